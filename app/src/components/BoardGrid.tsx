@@ -8,7 +8,7 @@ import { GridNode as WasmGridNode, PathFindingAlgorithm, MazeType } from '../was
 import { NodeContextSelection, PlayType } from '../models/enums';
 import GridNode from './GridNode';
 import { PathFindingAlgorithmRun, PlayContext } from '../models/models';
-import { isAlgoRequest } from '../utilities/typeguards';
+import { isAlgoRequest, isUniverseError } from '../utilities/typeguards';
 
 export interface GridProps {
   gridWidth: number;
@@ -139,13 +139,26 @@ export default function BoardGrid({
     context: PlayContext;
   }): Promise<void> => {
     const useChunk = false; // For use if I want to :)
-    const maze = universe.generateMaze(mazeType);
+    const endpoints = playType === PlayType.Wall ? { start: getPoint(start), end: getPoint(end) } : undefined;
+    let maze: IGridNode[];
+
+    try {
+      maze = universe.generateMaze(mazeType, undefined, endpoints);
+    } catch (err) {
+      // Walls already on the board separate start and end, so there is no path for the maze to keep open.
+      if (!isUniverseError(err) || err.code !== 'invalidOptions') {
+        throw err;
+      }
+
+      maze = universe.generateMaze(mazeType);
+    }
+
     const chunks = maze
       .chunk(500)
       .select(c => c.toArray())
       .toArray();
 
-    const processNode = ({ x, y }: IGridNode): void => {
+    const processNode = ({ x, y, passable, weight: mazeWeight }: IGridNode): void => {
       const node = universe.getNode(x, y);
       const nodeKey = getKey(x, y);
 
@@ -153,7 +166,10 @@ export default function BoardGrid({
         return;
       }
 
-      if (playType === PlayType.Wall) {
+      // Passable maze nodes are weighted cells such as dungeon doorways rather than walls.
+      if (passable) {
+        setWeighted(node, nodeKey, mazeWeight);
+      } else if (playType === PlayType.Wall) {
         setWall(node, nodeKey);
       } else {
         setWeighted(node, nodeKey);
@@ -294,22 +310,27 @@ export default function BoardGrid({
       .toArray();
 
     const weightChunks = weights
-      .map<[Point, string]>(key => [getPoint(key), key])
+      .map<[Point, string, number | undefined]>(entry => {
+        // Entries are "x,y,weight" since snapshot version 2; older runs store "x,y" with a shared weight.
+        const point = getPoint(entry);
+        const [, , cellWeight] = entry.split(',');
+        return [point, getKey(point), cellWeight === undefined ? weight : Number(cellWeight)];
+      })
       .filter(([{ x, y }]) => universe.hasNode(x, y))
       .chunk(350)
       .select(c => c.toArray())
       .toArray();
 
     const processChunk = async (
-      chunk: [Point, string][],
+      chunk: [Point, string, number?][],
       processor: (point: Point, key: string, weight?: number) => void
     ): Promise<void> => {
       for (let i = 0, j = chunk.length - 1; i < j; i++, j--) {
-        const [sPoint, sKey] = chunk[i];
-        const [ePoint, eKey] = chunk[j];
+        const [sPoint, sKey, sWeight] = chunk[i];
+        const [ePoint, eKey, eWeight] = chunk[j];
 
-        processor(sPoint, sKey, weight);
-        processor(ePoint, eKey, weight);
+        processor(sPoint, sKey, sWeight);
+        processor(ePoint, eKey, eWeight);
 
         await wait(5);
       }
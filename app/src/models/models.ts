@@ -30,7 +30,8 @@ export interface PathFindingAlgorithmRun {
   timestamp: number;
   start: string;
   end: string;
-  weight: number;
+  version?: number;
+  weight?: number;
   walls: string[];
   weights: string[];
 }
@@ -1,5 +1,5 @@
 import { PlayContext, PathFindingAlgorithmRun } from '../models/models';
-import { PathFindingAlgorithm } from '../wasm/algo_visualizer';
+import { IUniverseError, PathFindingAlgorithm } from '../wasm/algo_visualizer';
 
 export function isAlgoRequest(e: unknown): e is {
   algo: PathFindingAlgorithm;
@@ -19,3 +19,11 @@ export function isPathFindingAlgorithmRun(e: unknown): e is PathFindingAlgorithm
 
   return false;
 }
+
+export function isUniverseError(e: unknown): e is IUniverseError {
+  if (typeof (e as IUniverseError)?.code === 'string' && typeof (e as IUniverseError).message === 'string') {
+    return true;
+  }
+
+  return false;
+}
@@ -128,7 +128,7 @@ export async function drawPath(
     algorithmName: getAlgoNameText(algo),
     algorithm: algo,
     pathNodeCount: res.path.length,
-    pathCost: res.path.length + res.path.reduce((prev, curr) => prev + curr.weight, 0),
+    pathCost: res.statistics.pathCost,
     processedNodeCount: res.processed.length,
     timeTaken,
     timestamp: new Date().getTime(),
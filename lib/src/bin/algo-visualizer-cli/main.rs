@@ -0,0 +1,426 @@
+//! Runs searches and generates mazes from the command line, for scripting experiments and reproducing bug reports
+//! without the React app. Grids are read and written as snapshot JSON (`.json`), Moving AI maps (`.map`) or ASCII
+//! grids (`.txt`).
+
+#![allow(clippy::needless_return)]
+
+use std::{env, fmt::Debug, fs, path::Path, process};
+
+use algo_visualizer::universe::{
+    Connectivity, GridSnapshot, Heuristic, MazeOptions, MazeType, PathFindingAlgorithm, Universe,
+    MAX_GRID_CELLS,
+};
+
+const USAGE: &str = "usage:
+  algo-visualizer-cli search <grid> [<start x,y> <end x,y>] [options]
+      --algorithm <name>         defaults to astar
+      --connectivity <name>      four-way (default), eight-way or eight-way-corner-cutting
+      --heuristic <name>         defaults to the connectivity's natural heuristic
+      --heuristic-weight <w>     defaults to 1, must be finite and non-negative
+
+  algo-visualizer-cli maze <type> <width>x<height> <output> [options]
+      --seed <n>                 makes the maze reproducible
+      --braid <fraction>
+      --cave-fill <percent>
+      --cave-passes <n>          from 0 to 10
+      --doorway-weight <w>
+
+Grid files ending in .json are grid snapshots, files ending in .map are Moving AI maps and files ending
+in .txt are ASCII grids. A search on an ASCII grid defaults to the S and E marked on it.
+Names are case insensitive and may use dashes, so jump-point-search and JumpPointSearch both work.";
+
+/// A start and end, as x,y pairs.
+type Endpoints = ((i32, i32), (i32, i32));
+
+/// `--flag value` pairs, with the dashes stripped from the flag.
+type Flags<'a> = Vec<(&'a str, &'a str)>;
+
+const ALGORITHMS: [PathFindingAlgorithm; 8] = [
+    PathFindingAlgorithm::Astar,
+    PathFindingAlgorithm::AstarBidirectional,
+    PathFindingAlgorithm::Dijkstra,
+    PathFindingAlgorithm::BFS,
+    PathFindingAlgorithm::BFSBidirectional,
+    PathFindingAlgorithm::DFS,
+    PathFindingAlgorithm::GreedyBFS,
+    PathFindingAlgorithm::JumpPointSearch,
+];
+
+const CONNECTIVITIES: [Connectivity; 3] = [
+    Connectivity::FourWay,
+    Connectivity::EightWay,
+    Connectivity::EightWayCornerCutting,
+];
+
+const HEURISTICS: [Heuristic; 5] = [
+    Heuristic::Manhattan,
+    Heuristic::Euclidean,
+    Heuristic::Chebyshev,
+    Heuristic::Octile,
+    Heuristic::Zero,
+];
+
+const MAZE_TYPES: [MazeType; 12] = [
+    MazeType::Random25,
+    MazeType::Random50,
+    MazeType::Random75,
+    MazeType::RecursiveDivision,
+    MazeType::Prim,
+    MazeType::Kruskal,
+    MazeType::Wilson,
+    MazeType::AldousBroder,
+    MazeType::RecursiveBacktracker,
+    MazeType::HuntAndKill,
+    MazeType::Caves,
+    MazeType::Dungeon,
+];
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match run(&args) {
+        Ok(output) => print!("{}", output),
+        Err(err) => {
+            eprintln!("error: {}\nrun with no arguments for usage", err);
+            process::exit(1);
+        }
+    }
+}
+
+/// Runs the command described by `args`, returning what to print on success.
+fn run(args: &[String]) -> Result<String, String> {
+    return match args.first().map(String::as_str) {
+        Some("search") => search(&args[1..]),
+        Some("maze") => maze(&args[1..]),
+        Some("help" | "--help") | None => Ok(format!("{}\n", USAGE)),
+        Some(command) => Err(format!("unknown command \"{}\"", command)),
+    };
+}
+
+fn search(args: &[String]) -> Result<String, String> {
+    let (positional, flags) = split_args(args)?;
+
+    let (grid, endpoints) = match positional.as_slice() {
+        [grid] => (grid, None),
+        [grid, start, end] => (grid, Some((parse_point(start)?, parse_point(end)?))),
+        _ => {
+            return Err(
+                "search takes a grid file, optionally followed by a start and an end".to_string(),
+            )
+        }
+    };
+
+    let (mut universe, marked) = read_grid(grid)?;
+    let ((start_x, start_y), (end_x, end_y)) = match (endpoints, marked) {
+        (Some(endpoints), _) | (None, Some(endpoints)) => endpoints,
+        (None, None) => {
+            return Err(format!(
+                "{} does not mark a start and end, so give them",
+                grid
+            ))
+        }
+    };
+    let mut algorithm = PathFindingAlgorithm::Astar;
+
+    for (flag, value) in flags {
+        match flag {
+            "algorithm" => algorithm = parse_name(value, &ALGORITHMS)?,
+            "connectivity" => universe.connectivity = parse_name(value, &CONNECTIVITIES)?,
+            "heuristic" => universe.set_heuristic(Some(parse_name(value, &HEURISTICS)?)),
+            "heuristic-weight" => {
+                let weight: f64 = parse_value(flag, value)?;
+
+                if !weight.is_finite() || weight < 0.0 {
+                    return Err(format!(
+                        "--heuristic-weight must be a finite, non-negative number, got {}",
+                        value
+                    ));
+                }
+
+                universe.heuristic_weight = weight;
+            }
+            _ => return Err(format!("unknown search option --{}", flag)),
+        }
+    }
+
+    let result = universe
+        .find_path(start_x, start_y, end_x, end_y, algorithm, false)
+        .map_err(|err| err.to_string())?;
+
+    let path = match result.path.is_empty() {
+        true => "none".to_string(),
+        false => result
+            .path
+            .iter()
+            .map(|node| format!("{},{}", node.x, node.y))
+            .collect::<Vec<String>>()
+            .join(" "),
+    };
+
+    return Ok(format!(
+        "algorithm: {:?}\npath: {}\nlength: {}\ncost: {}\nexpanded: {}\n",
+        algorithm,
+        path,
+        result.statistics.path_length,
+        result.statistics.path_cost,
+        result.statistics.nodes_expanded
+    ));
+}
+
+fn maze(args: &[String]) -> Result<String, String> {
+    let (positional, flags) = split_args(args)?;
+
+    let [maze_type, size, output] = positional.as_slice() else {
+        return Err("maze takes a maze type, a size and an output file".to_string());
+    };
+
+    let maze_type = parse_name(maze_type, &MAZE_TYPES)?;
+    let (width, height): (u32, u32) = match size.split_once('x') {
+        Some((width, height)) => (parse_value("width", width)?, parse_value("height", height)?),
+        None => return Err(format!("invalid size \"{}\", expected WIDTHxHEIGHT", size)),
+    };
+
+    if width == 0 || height == 0 {
+        return Err(format!("invalid size \"{}\"", size));
+    }
+
+    if width
+        .checked_mul(height)
+        .is_none_or(|cells| cells > MAX_GRID_CELLS)
+    {
+        return Err(format!(
+            "size \"{}\" is larger than {} cells",
+            size, MAX_GRID_CELLS
+        ));
+    }
+
+    let mut options = MazeOptions::default();
+    let mut seed: Option<u32> = None;
+
+    for (flag, value) in flags {
+        match flag {
+            "seed" => seed = Some(parse_value(flag, value)?),
+            "braid" => options.braid = parse_value(flag, value)?,
+            "cave-fill" => options.cave_fill = parse_value(flag, value)?,
+            "cave-passes" => options.cave_passes = parse_value(flag, value)?,
+            "doorway-weight" => options.doorway_weight = parse_value(flag, value)?,
+            _ => return Err(format!("unknown maze option --{}", flag)),
+        }
+    }
+
+    let mut universe = Universe::new(width, height);
+    universe
+        .apply_maze(maze_type, seed, &options)
+        .map_err(|err| err.to_string())?;
+
+    write_grid(output, &universe)?;
+
+    return Ok(format!(
+        "wrote {:?} maze ({}x{}) to {}\n",
+        maze_type, width, height, output
+    ));
+}
+
+/// Splits arguments into positional ones and `--flag value` pairs, in order.
+fn split_args(args: &[String]) -> Result<(Vec<&str>, Flags<'_>), String> {
+    let mut positional = Vec::new();
+    let mut flags = Vec::new();
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.strip_prefix("--") {
+            Some(flag) => match args.next() {
+                Some(value) => flags.push((flag, value.as_str())),
+                None => return Err(format!("--{} is missing its value", flag)),
+            },
+            None => positional.push(arg.as_str()),
+        }
+    }
+
+    return Ok((positional, flags));
+}
+
+/// Reads the grid in `path`, along with the start and end when the file marks both.
+fn read_grid(path: &str) -> Result<(Universe, Option<Endpoints>), String> {
+    let contents =
+        fs::read_to_string(path).map_err(|err| format!("cannot read {}: {}", path, err))?;
+
+    return match extension(path) {
+        Some("json") => {
+            let snapshot: GridSnapshot = serde_json::from_str(&contents)
+                .map_err(|err| format!("invalid snapshot {}: {}", path, err))?;
+            let mut universe = Universe::new(1, 1);
+
+            universe
+                .load_snapshot(&snapshot)
+                .map_err(|err| format!("invalid snapshot {}: {}", path, err))?;
+
+            Ok((universe, None))
+        }
+        Some("map") => Universe::from_moving_ai_map(&contents)
+            .map(|universe| (universe, None))
+            .map_err(|err| format!("invalid map {}: {}", path, err)),
+        Some("txt") => {
+            let grid = Universe::from_ascii(&contents)
+                .map_err(|err| format!("invalid ascii grid {}: {}", path, err))?;
+            let endpoints = match (grid.start, grid.end) {
+                (Some(start), Some(end)) => Some(((start.x, start.y), (end.x, end.y))),
+                _ => None,
+            };
+
+            Ok((grid.universe, endpoints))
+        }
+        _ => Err(format!("{} is not a .json, .map or .txt grid", path)),
+    };
+}
+
+fn write_grid(path: &str, universe: &Universe) -> Result<(), String> {
+    let contents = match extension(path) {
+        Some("json") => serde_json::to_string_pretty(&universe.get_snapshot()).unwrap() + "\n",
+        Some("map") => universe.to_moving_ai_map(),
+        Some("txt") => universe.to_ascii(),
+        _ => return Err(format!("{} is not a .json, .map or .txt grid", path)),
+    };
+
+    return fs::write(path, contents).map_err(|err| format!("cannot write {}: {}", path, err));
+}
+
+fn extension(path: &str) -> Option<&str> {
+    return Path::new(path)
+        .extension()
+        .and_then(|extension| extension.to_str());
+}
+
+fn parse_point(point: &str) -> Result<(i32, i32), String> {
+    return match point.split_once(',') {
+        Some((x, y)) => Ok((parse_value("x", x)?, parse_value("y", y)?)),
+        None => Err(format!("invalid point \"{}\", expected x,y", point)),
+    };
+}
+
+fn parse_value<T: std::str::FromStr>(name: &str, value: &str) -> Result<T, String> {
+    return value
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid {} \"{}\"", name, value));
+}
+
+/// Finds the variant whose name matches `name`, ignoring case, dashes and underscores.
+fn parse_name<T: Copy + Debug>(name: &str, variants: &[T]) -> Result<T, String> {
+    let normalize = |name: &str| -> String {
+        return name
+            .chars()
+            .filter(|c| *c != '-' && *c != '_')
+            .collect::<String>()
+            .to_lowercase();
+    };
+
+    return variants
+        .iter()
+        .find(|variant| normalize(&format!("{:?}", variant)) == normalize(name))
+        .copied()
+        .ok_or_else(|| {
+            let names: Vec<String> = variants.iter().map(|v| format!("{:?}", v)).collect();
+            format!(
+                "unknown name \"{}\", expected one of {}",
+                name,
+                names.join(", ")
+            )
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        return args.iter().map(|arg| arg.to_string()).collect();
+    }
+
+    #[test]
+    fn names_are_matched_loosely() {
+        assert_eq!(
+            parse_name("jump-point-search", &ALGORITHMS),
+            Ok(PathFindingAlgorithm::JumpPointSearch)
+        );
+        assert_eq!(
+            parse_name("bfs", &ALGORITHMS),
+            Ok(PathFindingAlgorithm::BFS)
+        );
+        assert_eq!(
+            parse_name("eight_way", &CONNECTIVITIES),
+            Ok(Connectivity::EightWay)
+        );
+        assert!(parse_name("spiral", &MAZE_TYPES).is_err());
+    }
+
+    #[test]
+    fn generated_mazes_can_be_searched() {
+        let directory = env::temp_dir().join(format!("algo-visualizer-cli-{}", process::id()));
+        fs::create_dir_all(&directory).unwrap();
+
+        for file in ["maze.json", "maze.map", "maze.txt"] {
+            let path = directory.join(file).to_string_lossy().into_owned();
+
+            run(&args(&["maze", "prim", "21x15", &path, "--seed", "7"])).unwrap();
+
+            let output = run(&args(&[
+                "search",
+                &path,
+                "1,1",
+                "19,13",
+                "--algorithm",
+                "dijkstra",
+            ]))
+            .unwrap();
+
+            assert!(output.starts_with("algorithm: Dijkstra\npath: 1,1 "));
+            assert!(output.contains("\nexpanded: "));
+            assert!(!output.contains("path: none"));
+        }
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn ascii_grids_default_to_marked_endpoints() {
+        let path = env::temp_dir()
+            .join(format!("algo-visualizer-cli-{}.txt", process::id()))
+            .to_string_lossy()
+            .into_owned();
+        fs::write(&path, "S.#.\n..#E\n....\n").unwrap();
+
+        let output = run(&args(&["search", &path, "--algorithm", "bfs"])).unwrap();
+        let weight_error = run(&args(&["search", &path, "--heuristic-weight", "NaN"]));
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            weight_error,
+            Err("--heuristic-weight must be a finite, non-negative number, got NaN".to_string())
+        );
+        assert_eq!(
+            output,
+            "algorithm: BFS\npath: 0,0 0,1 0,2 1,2 2,2 3,2 3,1\nlength: 6\ncost: 6\nexpanded: 9\n"
+        );
+    }
+
+    #[test]
+    fn errors_are_reported() {
+        assert_eq!(
+            run(&args(&["maze", "prim", "21by15", "out.json"])),
+            Err("invalid size \"21by15\", expected WIDTHxHEIGHT".to_string())
+        );
+        assert_eq!(
+            run(&args(&["maze", "prim", "100000x100000", "out.json"])),
+            Err("size \"100000x100000\" is larger than 16777216 cells".to_string())
+        );
+        assert_eq!(
+            run(&args(&["search", "grid.json", "1,1"])),
+            Err("search takes a grid file, optionally followed by a start and an end".to_string())
+        );
+        assert_eq!(
+            run(&args(&["teleport"])),
+            Err("unknown command \"teleport\"".to_string())
+        );
+    }
+}
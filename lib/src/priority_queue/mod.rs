@@ -1,2 +1,3 @@
+#[allow(clippy::module_inception)]
 pub mod priority_queue;
 mod priority_queue_item;
@@ -21,10 +21,20 @@ where
         self.heap.len()
     }
 
-    pub fn enqueue(&mut self, item: T, priority: i32) {
+    pub fn enqueue(&mut self, item: T, priority: i64) {
         self.heap.push(PriorityQueueItem { item, priority })
     }
 
+    /// The item `dequeue` would return next, without removing it.
+    pub fn peek(&self) -> Option<&T> {
+        self.heap.peek().map(|item| &item.item)
+    }
+
+    /// Iterates over every queued item in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.heap.iter().map(|item| &item.item)
+    }
+
     pub fn dequeue(&mut self) -> Option<T> {
         match self.heap.pop() {
             Some(item) => Some(item.item),
@@ -48,6 +58,7 @@ mod tests {
         queue.enqueue(29, 2);
 
         assert_eq!(queue.count(), 3);
+        assert_eq!(queue.peek(), Some(&8));
 
         assert_eq!(queue.dequeue(), Some(8));
         assert_eq!(queue.dequeue(), Some(29));
@@ -1,7 +1,7 @@
 #[derive(Eq, Debug)]
 pub struct PriorityQueueItem<T> {
     pub item: T,
-    pub priority: i32,
+    pub priority: i64,
 }
 
 impl<T> PartialEq for PriorityQueueItem<T> {
@@ -30,6 +30,7 @@ mod tests {
     use super::*;
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn priority_queue_item() {
         let item_1 = PriorityQueueItem {
             item: 5,
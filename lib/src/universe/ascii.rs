@@ -0,0 +1,221 @@
+//! A plain text grid format for tests, bug reports and docs. Each line is a row and each character a cell:
+//!
+//! | Character | Cell                                                          |
+//! |-----------|---------------------------------------------------------------|
+//! | `#`       | wall                                                          |
+//! | `.`       | open, unweighted                                              |
+//! | `1`-`9`   | open with weight 1 to 9                                       |
+//! | `a`-`z`   | open with weight 10 to 35; heavier weights are written as `z` |
+//! | `S` / `E` | the start / end, open and unweighted                          |
+//! | `*`       | a node on the overlaid path                                   |
+//! | `+`       | a node the overlaid search processed but left off its path    |
+//!
+//! Overlay cells read back as open and unweighted, so a drawn search can be pasted straight back into a test.
+
+use std::collections::HashSet;
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use super::{GridNode, PathResult, Point, Universe, UniverseError};
+
+const MAX_ASCII_WEIGHT: i32 = 35;
+
+/// A universe read from text, along with the endpoints marked on it.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct AsciiGrid {
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
+    pub universe: Universe,
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
+    pub start: Option<Point>,
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
+    pub end: Option<Point>,
+}
+
+/// What to draw over the grid when writing it as text.
+#[derive(Default)]
+pub struct AsciiOverlay<'a> {
+    pub start: Option<Point>,
+    pub end: Option<Point>,
+    /// A search whose path and processed nodes are drawn. The endpoints are drawn over it.
+    pub result: Option<&'a PathResult>,
+}
+
+pub fn parse_ascii(ascii: &str) -> Result<AsciiGrid, UniverseError> {
+    let rows: Vec<&str> = ascii
+        .trim_matches(|c| c == '\n' || c == '\r')
+        .lines()
+        .map(|line| line.trim_end_matches('\r'))
+        .collect();
+
+    let width = rows.first().map_or(0, |row| row.chars().count());
+
+    if width == 0 {
+        return Err(UniverseError::InvalidAscii(
+            "ascii grid is empty".to_string(),
+        ));
+    }
+
+    let mut universe = Universe::new(width as u32, rows.len() as u32);
+    let mut start: Option<Point> = None;
+    let mut end: Option<Point> = None;
+
+    for (y, row) in rows.iter().enumerate() {
+        if row.chars().count() != width {
+            return Err(UniverseError::InvalidAscii(format!(
+                "ascii row {} has {} cells, expected {}",
+                y,
+                row.chars().count(),
+                width
+            )));
+        }
+
+        for (x, cell) in row.chars().enumerate() {
+            let point = Point {
+                x: x as i32,
+                y: y as i32,
+            };
+
+            match cell {
+                '.' | '*' | '+' => {}
+                '#' => universe.set_passable(point.x, point.y, false)?,
+                'S' | 'E' => {
+                    let (name, endpoint) = match cell {
+                        'S' => ("start", &mut start),
+                        _ => ("end", &mut end),
+                    };
+
+                    if endpoint.is_some() {
+                        return Err(UniverseError::InvalidAscii(format!(
+                            "ascii grid has more than one {}",
+                            name
+                        )));
+                    }
+
+                    *endpoint = Some(point);
+                }
+                _ => match cell.to_digit(MAX_ASCII_WEIGHT as u32 + 1) {
+                    Some(weight) if !cell.is_ascii_uppercase() => {
+                        universe.set_weight(point.x, point.y, weight as i32)?;
+                    }
+                    _ => {
+                        return Err(UniverseError::InvalidAscii(format!(
+                            "unsupported ascii cell '{}' at {},{}",
+                            cell, x, y
+                        )));
+                    }
+                },
+            }
+        }
+    }
+
+    return Ok(AsciiGrid {
+        universe,
+        start,
+        end,
+    });
+}
+
+pub fn write_ascii(universe: &Universe, overlay: &AsciiOverlay) -> String {
+    let (path, processed): (HashSet<&GridNode>, HashSet<&GridNode>) = match overlay.result {
+        Some(result) => (
+            result.path.iter().collect(),
+            result.processed.iter().collect(),
+        ),
+        None => (HashSet::new(), HashSet::new()),
+    };
+
+    let is_at = |endpoint: Option<Point>, node: &GridNode| {
+        return endpoint.is_some_and(|point| point.x == node.x && point.y == node.y);
+    };
+
+    let mut ascii = String::with_capacity(((universe.width + 1) * universe.height) as usize);
+
+    for y in 0..universe.height as i32 {
+        for x in 0..universe.width as i32 {
+            let node = universe.get_node_ref(x, y);
+
+            ascii.push(match node {
+                node if is_at(overlay.start, node) => 'S',
+                node if is_at(overlay.end, node) => 'E',
+                node if !node.passable => '#',
+                node if path.contains(node) => '*',
+                node if processed.contains(node) => '+',
+                node if node.weight <= 0 => '.',
+                node => char::from_digit(node.weight.min(MAX_ASCII_WEIGHT) as u32, 36).unwrap(),
+            });
+        }
+
+        ascii.push('\n');
+    }
+
+    return ascii;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::universe::PathFindingAlgorithm;
+
+    const GRID: &str = "\
+S..#....
+.#.#.9a.
+.#...#..
+.####.#E
+";
+
+    #[test]
+    fn ascii_round_trips() {
+        let grid = parse_ascii(GRID).unwrap();
+
+        assert_eq!(grid.universe.width, 8);
+        assert_eq!(grid.universe.height, 4);
+        assert_eq!(grid.start, Some(Point { x: 0, y: 0 }));
+        assert_eq!(grid.end, Some(Point { x: 7, y: 3 }));
+        assert!(!grid.universe.get_node(3, 0).unwrap().passable);
+        assert_eq!(grid.universe.get_node(5, 1).unwrap().weight, 9);
+        assert_eq!(grid.universe.get_node(6, 1).unwrap().weight, 10);
+
+        let overlay = AsciiOverlay {
+            start: grid.start,
+            end: grid.end,
+            ..Default::default()
+        };
+        assert_eq!(grid.universe.to_ascii_with_overlay(&overlay), GRID);
+        assert_eq!(grid.universe.to_ascii(), GRID.replace(['S', 'E'], "."));
+    }
+
+    #[test]
+    fn overlay_marks_path_and_processed_nodes() {
+        let grid = parse_ascii(GRID).unwrap();
+        let result = grid
+            .universe
+            .find_path(0, 0, 7, 3, PathFindingAlgorithm::BFS, false)
+            .unwrap();
+
+        let drawn = grid.universe.to_ascii_with_overlay(&AsciiOverlay {
+            start: grid.start,
+            end: grid.end,
+            result: Some(&result),
+        });
+
+        assert_eq!(drawn.matches('*').count(), result.path.len() - 2);
+        assert!(drawn.contains('+'));
+        assert!(drawn.starts_with("S**#"));
+
+        let redrawn = parse_ascii(&drawn).unwrap();
+        assert_eq!(redrawn.start, grid.start);
+        assert_eq!(redrawn.end, grid.end);
+        assert_eq!(redrawn.universe.to_ascii().matches('#').count(), 10);
+    }
+
+    #[test]
+    fn ascii_errors_are_descriptive() {
+        let error = |ascii: &str| parse_ascii(ascii).err().unwrap().to_string();
+
+        assert_eq!(error(""), "ascii grid is empty");
+        assert_eq!(error("..\n."), "ascii row 1 has 1 cells, expected 2");
+        assert_eq!(error(".X"), "unsupported ascii cell 'X' at 1,0");
+        assert_eq!(error("S.S"), "ascii grid has more than one start");
+    }
+}
@@ -0,0 +1,19 @@
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::wasm_bindgen;
+
+/// Movement costs are fixed-point so a diagonal step can cost √2 times an orthogonal one.
+pub const STRAIGHT_COST: i64 = 10_000;
+pub const DIAGONAL_COST: i64 = 14_142;
+
+/// Which neighbors a search may move to from any given node.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Connectivity {
+    /// Only the four orthogonal neighbors.
+    #[default]
+    FourWay,
+    /// All eight neighbors, but a diagonal step is only allowed when both orthogonal cells it passes are open.
+    EightWay,
+    /// All eight neighbors, and a diagonal step may cut past the corner of a single wall.
+    EightWayCornerCutting,
+}
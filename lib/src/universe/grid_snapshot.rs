@@ -1,10 +1,23 @@
 use serde::{Deserialize, Serialize};
 
+/// Snapshots without a version predate per-cell weights and share a single `weight` across all weighted cells.
+pub const LEGACY_SNAPSHOT_VERSION: u32 = 1;
+pub const SNAPSHOT_VERSION: u32 = 2;
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct GridSnapshot {
+    #[serde(default = "legacy_snapshot_version")]
+    pub version: u32,
     pub width: i32,
     pub height: i32,
-    pub weight: i32,
+    /// Weight of every cell in `weights`. Only read from version 1 snapshots.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<i32>,
     pub walls: Vec<String>,
+    /// Weighted cells as "x,y,weight", or "x,y" in version 1 snapshots.
     pub weights: Vec<String>,
 }
+
+fn legacy_snapshot_version() -> u32 {
+    return LEGACY_SNAPSHOT_VERSION;
+}
@@ -0,0 +1,14 @@
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::wasm_bindgen;
+
+/// Distance estimate used by A*, bidirectional A*, greedy best-first search and jump point search.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Heuristic {
+    Manhattan,
+    Euclidean,
+    Chebyshev,
+    Octile,
+    /// Always estimates zero, which turns A* into Dijkstra's algorithm.
+    Zero,
+}
@@ -0,0 +1,14 @@
+use serde::Serialize;
+
+use super::{GridNode, MazeEvent};
+
+/// A generated maze together with the steps that built it.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MazeConstruction {
+    /// The same nodes `generate_maze` returns.
+    pub maze: Vec<GridNode>,
+    /// Whether construction starts from a grid of walls and carves into it, rather than from an open grid.
+    pub starts_filled: bool,
+    pub events: Vec<MazeEvent>,
+}
@@ -0,0 +1,28 @@
+use serde::Serialize;
+
+use super::GridNode;
+
+/// A single change a maze generator made to the grid, recorded in order when events are requested.
+/// Each node carries its state after the change.
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum MazeEvent {
+    WallAdded {
+        node: GridNode,
+    },
+    WallRemoved {
+        node: GridNode,
+    },
+    /// A node opened up as part of a room or maze cell, as opposed to a wall knocked out between two cells.
+    CellCarved {
+        node: GridNode,
+    },
+}
+
+impl MazeEvent {
+    pub(super) fn record(events: &mut Option<Vec<MazeEvent>>, event: impl FnOnce() -> MazeEvent) {
+        if let Some(events) = events {
+            events.push(event());
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use rand::Rng;
+use rand_chacha::ChaCha8Rng;
+
+use super::{GridNode, MazeEvent, Universe};
+
+/// The cells of a perfect maze: nodes at odd coordinates separated by single-node walls, the same
+/// layout `recursive_division_maze` produces. Everything starts as wall and generators carve cells
+/// and the passages between them.
+pub struct MazeLattice<'a> {
+    universe: &'a Universe,
+    columns: i32,
+    rows: i32,
+    open: Vec<bool>,
+    events: &'a mut Option<Vec<MazeEvent>>,
+}
+
+impl<'a> MazeLattice<'a> {
+    pub fn new(universe: &'a Universe, events: &'a mut Option<Vec<MazeEvent>>) -> Self {
+        return MazeLattice {
+            universe,
+            columns: (universe.width as i32 - 1) / 2,
+            rows: (universe.height as i32 - 1) / 2,
+            open: vec![false; (universe.width * universe.height) as usize],
+            events,
+        };
+    }
+
+    pub fn cell_count(&self) -> usize {
+        return (self.columns * self.rows) as usize;
+    }
+
+    pub fn is_carved(&self, cell: usize) -> bool {
+        let (x, y) = self.position(cell);
+        return self.open[self.universe.get_index(x, y)];
+    }
+
+    /// Cells one step away on the lattice, in a fixed order.
+    pub fn neighbors(&self, cell: usize) -> Vec<usize> {
+        let column = cell as i32 % self.columns;
+        let row = cell as i32 / self.columns;
+        let mut neighbors = Vec::with_capacity(4);
+
+        for (direction_x, direction_y) in [(0, -1), (0, 1), (-1, 0), (1, 0)] {
+            let neighbor_column = column + direction_x;
+            let neighbor_row = row + direction_y;
+
+            if neighbor_column >= 0
+                && neighbor_column < self.columns
+                && neighbor_row >= 0
+                && neighbor_row < self.rows
+            {
+                neighbors.push((neighbor_row * self.columns + neighbor_column) as usize);
+            }
+        }
+
+        return neighbors;
+    }
+
+    pub fn carve_cell(&mut self, cell: usize) {
+        let (x, y) = self.position(cell);
+        self.open_node(x, y, true);
+    }
+
+    /// Carves `to` and the wall between it and the neighboring cell `from`.
+    pub fn carve_passage(&mut self, from: usize, to: usize) {
+        let (from_x, from_y) = self.position(from);
+        let (to_x, to_y) = self.position(to);
+
+        self.open_node((from_x + to_x) / 2, (from_y + to_y) / 2, false);
+        self.open_node(to_x, to_y, true);
+    }
+
+    /// Every node left uncarved, in grid order.
+    pub fn into_walls(self) -> Vec<GridNode> {
+        return self
+            .open
+            .iter()
+            .enumerate()
+            .filter(|(_, open)| !**open)
+            .map(|(index, _)| *self.universe.get_node_by_index(index))
+            .collect();
+    }
+
+    fn position(&self, cell: usize) -> (i32, i32) {
+        let column = cell as i32 % self.columns;
+        let row = cell as i32 / self.columns;
+
+        return (column * 2 + 1, row * 2 + 1);
+    }
+
+    fn open_node(&mut self, x: i32, y: i32, cell: bool) {
+        let index = self.universe.get_index(x, y);
+
+        if self.open[index] {
+            return;
+        }
+
+        self.open[index] = true;
+
+        let node = GridNode {
+            passable: true,
+            ..*self.universe.get_node_by_index(index)
+        };
+        MazeEvent::record(self.events, || match cell {
+            true => MazeEvent::CellCarved { node },
+            false => MazeEvent::WallRemoved { node },
+        });
+    }
+}
+
+/// Picks an index below `len`. Sampling a u32 keeps the sequence identical on 32-bit wasm and 64-bit native targets.
+pub fn random_index(rng: &mut ChaCha8Rng, len: usize) -> usize {
+    return rng.gen_range(0..len as u32) as usize;
+}
+
+/// Fisher-Yates shuffle built on `random_index`, so it is portable in the same way.
+pub fn shuffle<T>(rng: &mut ChaCha8Rng, items: &mut [T]) {
+    for i in (1..items.len()).rev() {
+        items.swap(i, random_index(rng, i + 1));
+    }
+}
@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+
+use super::{Point, UniverseError};
+
+/// Each pass walks the whole grid, and caves settle well before this many.
+const MAX_CAVE_PASSES: u32 = 10;
+
+/// Tuning shared by `generate_maze`, `generate_maze_construction` and `apply_maze`. Fields missing when
+/// deserialized keep their `Default` values.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MazeOptions {
+    /// Percentage of nodes `MazeType::Caves` seeds as walls before smoothing.
+    pub cave_fill: u32,
+    /// Number of birth/survival smoothing passes `MazeType::Caves` runs over the seeded walls, at most 10.
+    pub cave_passes: u32,
+    /// Fraction of dead ends, from 0 to 1, to open up into loops once the maze is generated.
+    pub braid: f64,
+    /// Weight given to `MazeType::Dungeon` doorways, where corridors enter rooms. Zero leaves them unweighted.
+    pub doorway_weight: i32,
+    /// Whether `apply_maze` resets the grid before applying the maze, rather than adding to what is already there.
+    pub clear: bool,
+    /// Start of the path the maze must leave open. Given together with `end`, neither is ever walled and a corridor
+    /// is carved between them if the maze would otherwise separate them.
+    pub start: Option<Point>,
+    pub end: Option<Point>,
+}
+
+impl Default for MazeOptions {
+    fn default() -> Self {
+        return MazeOptions {
+            cave_fill: 45,
+            cave_passes: 4,
+            braid: 0.0,
+            doorway_weight: 0,
+            clear: false,
+            start: None,
+            end: None,
+        };
+    }
+}
+
+impl MazeOptions {
+    pub(super) fn validate(&self) -> Result<(), UniverseError> {
+        if self.cave_fill > 100 {
+            return Err(UniverseError::InvalidOptions(format!(
+                "cave fill must be a percentage, got {}",
+                self.cave_fill
+            )));
+        }
+
+        if self.cave_passes > MAX_CAVE_PASSES {
+            return Err(UniverseError::InvalidOptions(format!(
+                "cave passes must be between 0 and {}, got {}",
+                MAX_CAVE_PASSES, self.cave_passes
+            )));
+        }
+
+        if !(0.0..=1.0).contains(&self.braid) {
+            return Err(UniverseError::InvalidOptions(format!(
+                "braid factor must be between 0 and 1, got {}",
+                self.braid
+            )));
+        }
+
+        if self.doorway_weight < 0 {
+            return Err(UniverseError::InvalidOptions(format!(
+                "doorway weight cannot be negative, got {}",
+                self.doorway_weight
+            )));
+        }
+
+        if self.start.is_some() != self.end.is_some() {
+            return Err(UniverseError::InvalidOptions(
+                "maze start and end must be given together".to_string(),
+            ));
+        }
+
+        return Ok(());
+    }
+}
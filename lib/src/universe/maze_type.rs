@@ -1,9 +1,41 @@
+#[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::wasm_bindgen;
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum MazeType {
     Random25,
     Random50,
     Random75,
     RecursiveDivision,
+    /// A perfect maze grown with randomized Prim's algorithm, full of short, branchy dead ends.
+    Prim,
+    /// A perfect maze built by knocking down shuffled walls between cells that are not yet connected.
+    Kruskal,
+    /// A uniformly random perfect maze built from loop-erased random walks.
+    Wilson,
+    /// A uniformly random perfect maze built from a single random walk, which is slow to finish on large grids.
+    AldousBroder,
+    /// A perfect maze of long, winding corridors carved by a depth-first walk that backs up at dead ends.
+    RecursiveBacktracker,
+    /// Like the recursive backtracker, but a stuck walk restarts next to the first uncarved cell instead of backing up.
+    HuntAndKill,
+    /// Organic caverns grown from random walls by cellular-automaton smoothing, with isolated pockets filled in.
+    Caves,
+    /// Rectangular rooms placed by binary space partitioning and joined by corridors, like a roguelike level.
+    Dungeon,
+}
+
+impl MazeType {
+    /// Whether the generator carves into a grid of walls rather than adding walls to an open grid.
+    pub(super) fn starts_filled(self) -> bool {
+        return !matches!(
+            self,
+            MazeType::Random25
+                | MazeType::Random50
+                | MazeType::Random75
+                | MazeType::RecursiveDivision
+                | MazeType::Caves
+        );
+    }
 }
@@ -1,17 +1,53 @@
+pub use ascii::{AsciiGrid, AsciiOverlay};
+pub use connectivity::Connectivity;
 pub use grid_node::GridNode;
 pub use grid_snapshot::GridSnapshot;
+pub use heuristic::Heuristic;
+pub use maze_construction::MazeConstruction;
+pub use maze_event::MazeEvent;
+pub use maze_options::MazeOptions;
 pub use maze_type::MazeType;
+pub use moving_ai::ScenarioQuery;
+pub use path_result::PathResult;
 pub use pathfinding_algorithm::PathFindingAlgorithm;
-pub use universe::Universe;
+pub use point::Point;
+pub use search_session::SearchSession;
+pub use search_statistics::SearchStatistics;
+pub use search_step::SearchStep;
+pub use terrain_options::TerrainOptions;
+pub use trace_event::{TraceDirection, TraceEvent};
+pub use universe::{Universe, MAX_GRID_CELLS};
+pub use universe_error::UniverseError;
 
+use connectivity::{DIAGONAL_COST, STRAIGHT_COST};
+use maze_lattice::MazeLattice;
 use orientation::Orientation;
-use path_result::PathResult;
+use search_state::SearchState;
+use value_noise::ValueNoise;
 
+pub mod ascii;
+pub mod connectivity;
 pub mod grid_node;
 pub mod grid_snapshot;
+pub mod heuristic;
+pub mod maze_construction;
+pub mod maze_event;
+pub mod maze_options;
 pub mod maze_type;
+pub mod moving_ai;
+pub mod path_result;
 pub mod pathfinding_algorithm;
+pub mod point;
+pub mod search_session;
+pub mod search_statistics;
+pub mod search_step;
+pub mod terrain_options;
+pub mod trace_event;
+#[allow(clippy::module_inception)]
 pub mod universe;
+pub mod universe_error;
 
+mod maze_lattice;
 mod orientation;
-mod path_result;
+mod search_state;
+mod value_noise;
@@ -0,0 +1,358 @@
+//! Reading and writing the grid benchmark formats published by the Moving AI Lab
+//! (https://movingai.com/benchmarks/formats.html).
+
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use super::{Universe, UniverseError};
+
+/// A single start/goal query from a `.scen` file.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ScenarioQuery {
+    pub bucket: u32,
+    /// Name of the `.map` file the query was generated for.
+    pub map: String,
+    pub map_width: u32,
+    pub map_height: u32,
+    pub start_x: i32,
+    pub start_y: i32,
+    pub goal_x: i32,
+    pub goal_y: i32,
+    /// Optimal eight-way path length without corner cutting, with diagonal steps costing √2.
+    pub optimal_length: f64,
+}
+
+/// Parses a `.map` file. '@', 'T' and 'O' become walls, '.' and 'G' stay passable.
+pub fn parse_map(map: &str) -> Result<Universe, UniverseError> {
+    let mut lines = map.lines().map(|line| line.trim_end_matches('\r'));
+    let mut width: Option<u32> = None;
+    let mut height: Option<u32> = None;
+
+    loop {
+        let line = match lines.next() {
+            Some(line) => line.trim(),
+            None => {
+                return Err(UniverseError::InvalidMap(
+                    "map is missing its \"map\" line".to_string(),
+                ));
+            }
+        };
+
+        if line == "map" {
+            break;
+        }
+
+        match line.split_once(char::is_whitespace) {
+            Some(("type", kind)) if kind.trim() == "octile" => {}
+            Some(("type", kind)) => {
+                return Err(UniverseError::InvalidMap(format!(
+                    "unsupported map type \"{}\"",
+                    kind.trim()
+                )));
+            }
+            Some(("width", value)) => width = Some(parse_header_value("width", value)?),
+            Some(("height", value)) => height = Some(parse_header_value("height", value)?),
+            _ if line.is_empty() => {}
+            _ => {
+                return Err(UniverseError::InvalidMap(format!(
+                    "unexpected map header line \"{}\"",
+                    line
+                )))
+            }
+        }
+    }
+
+    let (width, height) = match (width, height) {
+        (Some(width), Some(height)) if width > 0 && height > 0 => (width, height),
+        (Some(width), Some(height)) => {
+            return Err(UniverseError::InvalidMap(format!(
+                "invalid map dimensions {}x{}",
+                width, height
+            )));
+        }
+        _ => {
+            return Err(UniverseError::InvalidMap(
+                "map is missing its width or height".to_string(),
+            ))
+        }
+    };
+
+    let rows: Vec<&str> = lines.by_ref().take(height as usize).collect();
+
+    if rows.len() != height as usize {
+        return Err(UniverseError::InvalidMap(format!(
+            "map has {} rows, expected {}",
+            rows.len(),
+            height
+        )));
+    }
+
+    for (y, row) in rows.iter().enumerate() {
+        if row.chars().count() != width as usize {
+            return Err(UniverseError::InvalidMap(format!(
+                "map row {} has {} cells, expected {}",
+                y,
+                row.chars().count(),
+                width
+            )));
+        }
+
+        if let Some((x, cell)) = row
+            .chars()
+            .enumerate()
+            .find(|(_, cell)| !matches!(cell, '.' | 'G' | '@' | 'T' | 'O'))
+        {
+            return Err(UniverseError::InvalidMap(format!(
+                "unsupported map cell '{}' at {},{}",
+                cell, x, y
+            )));
+        }
+    }
+
+    if Universe::cell_count(width, height).is_none() {
+        return Err(UniverseError::InvalidMap(format!(
+            "map dimensions {}x{} are too large",
+            width, height
+        )));
+    }
+
+    let mut universe = Universe::new(width, height);
+
+    for (y, row) in rows.iter().enumerate() {
+        for (x, cell) in row.chars().enumerate() {
+            if matches!(cell, '@' | 'T' | 'O') {
+                universe.set_passable(x as i32, y as i32, false)?;
+            }
+        }
+    }
+
+    if lines.any(|line| !line.trim().is_empty()) {
+        return Err(UniverseError::InvalidMap(format!(
+            "map has more than {} rows",
+            height
+        )));
+    }
+
+    return Ok(universe);
+}
+
+/// Writes the universe as a `.map` file. Walls are written as '@' and every other cell as '.', so weights are lost.
+pub fn write_map(universe: &Universe) -> String {
+    let mut map = format!(
+        "type octile\nheight {}\nwidth {}\nmap\n",
+        universe.height, universe.width
+    );
+
+    for y in 0..universe.height as i32 {
+        for x in 0..universe.width as i32 {
+            map.push(if universe.get_node_ref(x, y).passable {
+                '.'
+            } else {
+                '@'
+            });
+        }
+
+        map.push('\n');
+    }
+
+    return map;
+}
+
+/// Parses a `.scen` file into its queries, in file order.
+pub fn parse_scenario(scenario: &str) -> Result<Vec<ScenarioQuery>, UniverseError> {
+    let mut queries = Vec::new();
+
+    for (line_index, line) in scenario.lines().enumerate() {
+        let line = line.trim();
+
+        if line.is_empty() {
+            continue;
+        }
+
+        if line_index == 0 && line.starts_with("version") {
+            match line.split_whitespace().nth(1) {
+                Some("1" | "1.0") => continue,
+                version => {
+                    return Err(UniverseError::InvalidScenario(format!(
+                        "unsupported scenario version \"{}\"",
+                        version.unwrap_or_default()
+                    )));
+                }
+            }
+        }
+
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let line_number = line_index + 1;
+
+        if fields.len() != 9 {
+            return Err(UniverseError::InvalidScenario(format!(
+                "scenario line {} has {} fields, expected 9",
+                line_number,
+                fields.len()
+            )));
+        }
+
+        queries.push(ScenarioQuery {
+            bucket: parse_scenario_field(&fields, 0, "bucket", line_number)?,
+            map: fields[1].to_string(),
+            map_width: parse_scenario_field(&fields, 2, "map width", line_number)?,
+            map_height: parse_scenario_field(&fields, 3, "map height", line_number)?,
+            start_x: parse_scenario_field(&fields, 4, "start x", line_number)?,
+            start_y: parse_scenario_field(&fields, 5, "start y", line_number)?,
+            goal_x: parse_scenario_field(&fields, 6, "goal x", line_number)?,
+            goal_y: parse_scenario_field(&fields, 7, "goal y", line_number)?,
+            optimal_length: parse_scenario_field(&fields, 8, "optimal length", line_number)?,
+        });
+    }
+
+    return Ok(queries);
+}
+
+/// Writes queries as a version 1 `.scen` file.
+pub fn write_scenario(queries: &[ScenarioQuery]) -> String {
+    let mut scenario = "version 1\n".to_string();
+
+    for query in queries {
+        scenario.push_str(&format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{:.8}\n",
+            query.bucket,
+            query.map,
+            query.map_width,
+            query.map_height,
+            query.start_x,
+            query.start_y,
+            query.goal_x,
+            query.goal_y,
+            query.optimal_length
+        ));
+    }
+
+    return scenario;
+}
+
+fn parse_scenario_field<T: FromStr>(
+    fields: &[&str],
+    index: usize,
+    name: &str,
+    line_number: usize,
+) -> Result<T, UniverseError> {
+    return fields[index].parse().map_err(|_| {
+        UniverseError::InvalidScenario(format!(
+            "scenario line {} has an invalid {} \"{}\"",
+            line_number, name, fields[index]
+        ))
+    });
+}
+
+fn parse_header_value(name: &str, value: &str) -> Result<u32, UniverseError> {
+    return value.trim().parse().map_err(|_| {
+        UniverseError::InvalidMap(format!("invalid map {} \"{}\"", name, value.trim()))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::universe::{Connectivity, PathFindingAlgorithm};
+
+    const MAP: &str = "type octile\nheight 4\nwidth 5\nmap\n.....\n.@@T.\n..O..\nG....\n";
+
+    #[test]
+    fn map_round_trips() {
+        let universe = parse_map(MAP).unwrap();
+
+        assert_eq!(universe.width, 5);
+        assert_eq!(universe.height, 4);
+        assert!(!universe.get_node(1, 1).unwrap().passable);
+        assert!(!universe.get_node(3, 1).unwrap().passable);
+        assert!(!universe.get_node(2, 2).unwrap().passable);
+        assert!(universe.get_node(0, 3).unwrap().passable);
+
+        assert_eq!(
+            write_map(&universe),
+            MAP.replace(['T', 'O'], "@").replace('G', ".")
+        );
+    }
+
+    fn map_error(map: &str) -> String {
+        return parse_map(map).err().unwrap().to_string();
+    }
+
+    #[test]
+    fn map_errors_are_descriptive() {
+        assert_eq!(
+            map_error("type octile\nheight 2\nwidth 2\nmap\n..\n."),
+            "map row 1 has 1 cells, expected 2"
+        );
+        assert_eq!(
+            map_error("type octile\nheight 1\nwidth 2\nmap\n.W"),
+            "unsupported map cell 'W' at 1,0"
+        );
+        assert_eq!(
+            map_error("type hex\nheight 1\nwidth 1\nmap\n."),
+            "unsupported map type \"hex\""
+        );
+        assert_eq!(
+            map_error("type octile\nheight 100000\nwidth 100000\nmap\n.\n"),
+            "map has 1 rows, expected 100000"
+        );
+    }
+
+    #[test]
+    fn scenario_queries_match_search_costs() {
+        let scenario = "version 1\n\
+            0\ttest.map\t5\t4\t0\t0\t4\t0\t4.00000000\n\
+            1\ttest.map\t5\t4\t0\t0\t4\t3\t6.41421356\n\
+            2\topen.map\t40\t30\t0\t0\t39\t29\t51.01219331\n";
+
+        let queries = parse_scenario(scenario).unwrap();
+        assert_eq!(queries.len(), 3);
+        assert_eq!(queries[1].goal_y, 3);
+        assert_eq!(parse_scenario(&write_scenario(&queries)).unwrap(), queries);
+
+        // An open map, so the last query is one long diagonal run that exposes any rounding of √2.
+        let open_map = "type octile\nheight 30\nwidth 40\nmap\n".to_string()
+            + &(".".repeat(40) + "\n").repeat(30);
+
+        for algorithm in [PathFindingAlgorithm::Astar, PathFindingAlgorithm::Dijkstra] {
+            for query in &queries {
+                let mut universe = match query.map.as_str() {
+                    "open.map" => parse_map(&open_map).unwrap(),
+                    _ => parse_map(MAP).unwrap(),
+                };
+                universe.connectivity = Connectivity::EightWay;
+
+                let result = universe
+                    .find_path(
+                        query.start_x,
+                        query.start_y,
+                        query.goal_x,
+                        query.goal_y,
+                        algorithm,
+                        false,
+                    )
+                    .unwrap();
+
+                assert!((result.statistics.path_cost - query.optimal_length).abs() < 1e-3);
+            }
+        }
+    }
+
+    #[test]
+    fn scenario_errors_are_descriptive() {
+        assert_eq!(
+            parse_scenario("version 1\n0\tx.map\t5\t4\t0\t0\t4\n")
+                .unwrap_err()
+                .to_string(),
+            "scenario line 2 has 7 fields, expected 9"
+        );
+        assert_eq!(
+            parse_scenario("version 1\n0\tx.map\t5\t4\ta\t0\t4\t0\t4\n")
+                .unwrap_err()
+                .to_string(),
+            "scenario line 2 has an invalid start x \"a\""
+        );
+    }
+}
@@ -1,9 +1,12 @@
 use serde::Serialize;
 
-use super::GridNode;
+use super::{GridNode, SearchStatistics, TraceEvent};
 
 #[derive(Serialize, Debug)]
 pub struct PathResult {
     pub path: Vec<GridNode>,
     pub processed: Vec<GridNode>,
+    pub statistics: SearchStatistics,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trace: Option<Vec<TraceEvent>>,
 }
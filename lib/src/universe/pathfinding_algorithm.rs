@@ -1,6 +1,8 @@
+#[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::wasm_bindgen;
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum PathFindingAlgorithm {
     Astar,
     AstarBidirectional,
@@ -9,4 +11,5 @@ pub enum PathFindingAlgorithm {
     BFSBidirectional,
     DFS,
     GreedyBFS,
+    JumpPointSearch,
 }
@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+
+/// A grid coordinate.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default, Hash)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
@@ -0,0 +1,141 @@
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use super::{GridNode, PathFindingAlgorithm, SearchState, SearchStep, Universe, UniverseError};
+
+/// A search that the caller advances one expansion at a time, so it can be animated, paused or abandoned.
+/// The session works on a copy of the universe taken when it was created.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct SearchSession {
+    universe: Universe,
+    state: SearchState,
+}
+
+impl SearchSession {
+    pub fn new(
+        universe: &Universe,
+        start_x: i32,
+        start_y: i32,
+        end_x: i32,
+        end_y: i32,
+        algorithm: PathFindingAlgorithm,
+    ) -> Result<SearchSession, UniverseError> {
+        let universe = universe.clone();
+        let start = universe.check_bounds(start_x, start_y)?;
+        let end = universe.check_bounds(end_x, end_y)?;
+        let state = SearchState::new(&universe, start, end, algorithm, false)?;
+
+        return Ok(SearchSession { universe, state });
+    }
+
+    pub fn step(&mut self) -> SearchStep {
+        return self.step_n(1);
+    }
+
+    /// Runs up to `count` expansions and reports what changed, stopping early once the search finishes.
+    pub fn step_n(&mut self, count: u32) -> SearchStep {
+        let processed_before = self.state.processed().len();
+
+        for _ in 0..count {
+            if self.state.is_finished() {
+                break;
+            }
+
+            self.state.step(&self.universe);
+        }
+
+        return SearchStep {
+            processed: self.state.processed()[processed_before..].to_vec(),
+            frontier: self.state.frontier(&self.universe),
+            path: self.state.path().to_vec(),
+            finished: self.state.is_finished(),
+        };
+    }
+
+    pub fn frontier(&self) -> Vec<GridNode> {
+        return self.state.frontier(&self.universe);
+    }
+
+    pub fn path(&self) -> &[GridNode] {
+        return self.state.path();
+    }
+
+    pub fn finished(&self) -> bool {
+        return self.state.is_finished();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stepping_matches_find_path() {
+        let mut universe = Universe::new(12, 8);
+
+        for y in 0..6 {
+            universe.set_passable(5, y, false).unwrap();
+        }
+
+        universe.set_weight(8, 3, 4).unwrap();
+
+        for algorithm in [
+            PathFindingAlgorithm::Astar,
+            PathFindingAlgorithm::AstarBidirectional,
+            PathFindingAlgorithm::Dijkstra,
+            PathFindingAlgorithm::BFS,
+            PathFindingAlgorithm::BFSBidirectional,
+            PathFindingAlgorithm::DFS,
+            PathFindingAlgorithm::GreedyBFS,
+        ] {
+            let expected = universe.find_path(1, 1, 10, 1, algorithm, false).unwrap();
+            let mut session = SearchSession::new(&universe, 1, 1, 10, 1, algorithm).unwrap();
+            let mut processed = Vec::new();
+
+            while !session.state.is_finished() {
+                let step = session.step_n(3);
+                processed.extend(step.processed);
+            }
+
+            assert_eq!(processed, expected.processed);
+            assert_eq!(session.state.path(), expected.path.as_slice());
+        }
+    }
+
+    #[test]
+    fn step_reports_frontier_until_finished() {
+        let universe = Universe::new(5, 5);
+        let mut session =
+            SearchSession::new(&universe, 0, 0, 4, 4, PathFindingAlgorithm::BFS).unwrap();
+
+        let first = session.step_n(1);
+
+        assert_eq!(first.processed, vec![universe.get_node(0, 0).unwrap()]);
+        assert_eq!(first.frontier.len(), 2);
+        assert!(first.path.is_empty());
+        assert!(!first.finished);
+
+        let rest = session.step_n(u32::MAX);
+
+        assert!(rest.finished);
+        assert_eq!(rest.path.len(), 9);
+        assert!(session.step_n(1).processed.is_empty());
+    }
+
+    #[test]
+    fn finishes_with_empty_path_when_unreachable() {
+        let mut universe = Universe::new(5, 5);
+
+        for y in 0..5 {
+            universe.set_passable(2, y, false).unwrap();
+        }
+
+        let mut session =
+            SearchSession::new(&universe, 0, 0, 4, 4, PathFindingAlgorithm::Astar).unwrap();
+        let step = session.step_n(100);
+
+        assert!(step.finished);
+        assert!(step.path.is_empty());
+        assert!(step.frontier.is_empty());
+    }
+}
@@ -0,0 +1,992 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::PriorityQueue;
+
+use super::{
+    Connectivity, GridNode, PathFindingAlgorithm, PathResult, SearchStatistics, TraceDirection,
+    TraceEvent, Universe, UniverseError, STRAIGHT_COST,
+};
+
+/// The container a search direction pulls its next node from. Which one is used depends on the algorithm.
+/// Priority entries carry the g score they were queued with, so an entry superseded by a cheaper one can be told apart.
+enum Frontier {
+    Queue(VecDeque<usize>),
+    Stack(Vec<usize>),
+    Priority(PriorityQueue<(usize, i64)>),
+}
+
+impl Frontier {
+    fn new(algorithm: PathFindingAlgorithm) -> Self {
+        return match algorithm {
+            PathFindingAlgorithm::BFS | PathFindingAlgorithm::BFSBidirectional => {
+                Frontier::Queue(VecDeque::new())
+            }
+            PathFindingAlgorithm::DFS => Frontier::Stack(Vec::new()),
+            _ => Frontier::Priority(PriorityQueue::new()),
+        };
+    }
+
+    fn push(&mut self, index: usize, priority: i64, g: i64) {
+        match self {
+            Frontier::Queue(queue) => queue.push_back(index),
+            Frontier::Stack(stack) => stack.push(index),
+            Frontier::Priority(queue) => queue.enqueue((index, g), priority),
+        }
+    }
+
+    fn pop(&mut self) -> Option<usize> {
+        return match self {
+            Frontier::Queue(queue) => queue.pop_front(),
+            Frontier::Stack(stack) => stack.pop(),
+            Frontier::Priority(queue) => queue.dequeue().map(|(index, _)| index),
+        };
+    }
+
+    fn is_empty(&self) -> bool {
+        return self.len() == 0;
+    }
+
+    fn len(&self) -> usize {
+        return match self {
+            Frontier::Queue(queue) => queue.len(),
+            Frontier::Stack(stack) => stack.len(),
+            Frontier::Priority(queue) => queue.count(),
+        };
+    }
+
+    /// Queued nodes, leaving out priority entries whose g score has since been beaten by a cheaper entry.
+    fn indices(&self, g_score: &HashMap<usize, i64>) -> Vec<usize> {
+        return match self {
+            Frontier::Queue(queue) => queue.iter().copied().collect(),
+            Frontier::Stack(stack) => stack.to_vec(),
+            Frontier::Priority(queue) => queue
+                .iter()
+                .filter(|(index, g)| g_score.get(index) == Some(g))
+                .map(|(index, _)| *index)
+                .collect(),
+        };
+    }
+}
+
+/// Bookkeeping for one direction of a search. Bidirectional searches run two of these towards each other.
+struct SearchDirection {
+    algorithm: PathFindingAlgorithm,
+    kind: TraceDirection,
+    target: usize,
+    frontier: Frontier,
+    came_from: HashMap<usize, usize>,
+    g_score: HashMap<usize, i64>,
+    visited: HashSet<usize>,
+    expanded: HashSet<usize>,
+    generated: usize,
+    re_expansions: usize,
+}
+
+impl SearchDirection {
+    fn new(
+        algorithm: PathFindingAlgorithm,
+        kind: TraceDirection,
+        universe: &Universe,
+        root: usize,
+        target: usize,
+        trace: &mut Option<Vec<TraceEvent>>,
+    ) -> Self {
+        let mut direction = SearchDirection {
+            algorithm,
+            kind,
+            target,
+            frontier: Frontier::new(algorithm),
+            came_from: HashMap::new(),
+            g_score: HashMap::new(),
+            visited: HashSet::new(),
+            expanded: HashSet::new(),
+            generated: 0,
+            re_expansions: 0,
+        };
+
+        direction.g_score.insert(root, 0);
+        direction.enqueue(universe, root, trace);
+
+        // DFS marks nodes as visited when they are expanded rather than when they are discovered.
+        if algorithm != PathFindingAlgorithm::DFS {
+            direction.visited.insert(root);
+        }
+
+        return direction;
+    }
+
+    /// The g, h and f values of `index` as this direction sees them, where the algorithm tracks them.
+    fn scores(&self, universe: &Universe, index: usize) -> (Option<i64>, Option<i64>, Option<i64>) {
+        let node = universe.get_node_by_index(index);
+        let g = self.g_score.get(&index).copied();
+
+        let h = match self.algorithm {
+            PathFindingAlgorithm::Astar
+            | PathFindingAlgorithm::AstarBidirectional
+            | PathFindingAlgorithm::GreedyBFS
+            | PathFindingAlgorithm::JumpPointSearch => {
+                Some(universe.heuristic(node, universe.get_node_by_index(self.target)))
+            }
+            _ => None,
+        };
+
+        let f = match self.algorithm {
+            PathFindingAlgorithm::Dijkstra => g,
+            PathFindingAlgorithm::Astar
+            | PathFindingAlgorithm::AstarBidirectional
+            | PathFindingAlgorithm::JumpPointSearch => g.zip(h).map(|(g, h)| g + h),
+            PathFindingAlgorithm::GreedyBFS => h.map(|h| h + universe.get_weight_cost(node)),
+            _ => None,
+        };
+
+        return (g, h, f);
+    }
+
+    fn enqueue(&mut self, universe: &Universe, index: usize, trace: &mut Option<Vec<TraceEvent>>) {
+        let (g, h, f) = self.scores(universe, index);
+        let priority = match self.frontier {
+            Frontier::Priority(_) => f,
+            _ => None,
+        };
+
+        self.frontier
+            .push(index, -priority.unwrap_or(0), g.unwrap_or(0));
+        self.generated += 1;
+
+        record(trace, || TraceEvent::Enqueue {
+            node: *universe.get_node_by_index(index),
+            direction: self.kind,
+            priority: priority.map(cost_units),
+            g: g.map(cost_units),
+            h: h.map(cost_units),
+            f: f.map(cost_units),
+        });
+    }
+
+    /// Queues `index` again if reaching it through `from` for `tentative_g_score` beats its best known cost.
+    fn relax(
+        &mut self,
+        universe: &Universe,
+        index: usize,
+        from: usize,
+        tentative_g_score: i64,
+        trace: &mut Option<Vec<TraceEvent>>,
+    ) {
+        let old_g_score = self.g_score.get(&index).copied();
+
+        if tentative_g_score >= old_g_score.unwrap_or(i64::MAX) {
+            return;
+        }
+
+        self.g_score.insert(index, tentative_g_score);
+        self.came_from.insert(index, from);
+
+        record(trace, || {
+            let (_, h, f) = self.scores(universe, index);
+
+            TraceEvent::Relax {
+                node: *universe.get_node_by_index(index),
+                from: *universe.get_node_by_index(from),
+                direction: self.kind,
+                old_g: old_g_score.map(cost_units),
+                new_g: cost_units(tentative_g_score),
+                h: h.map(cost_units),
+                f: f.map(cost_units),
+            }
+        });
+
+        self.enqueue(universe, index, trace);
+    }
+
+    /// Drops queued entries for nodes that have since been reached more cheaply, so the next pop is a live entry and
+    /// popping an already expanded node means its cost really improved.
+    fn discard_stale(&mut self) {
+        if let Frontier::Priority(queue) = &mut self.frontier {
+            while let Some(&(index, g)) = queue.peek() {
+                if self.g_score.get(&index) == Some(&g) {
+                    break;
+                }
+
+                queue.dequeue();
+            }
+        }
+    }
+
+    /// Follows `came_from` from `index` back to the root of this direction.
+    fn chain(&self, index: usize) -> Vec<usize> {
+        let mut chain = vec![index];
+        let mut current = index;
+
+        while let Some(next) = self.came_from.get(&current) {
+            current = *next;
+            chain.push(current);
+        }
+
+        return chain;
+    }
+}
+
+/// Appends the event to the trace when tracing is enabled. The event is only built if it will be kept.
+fn record(trace: &mut Option<Vec<TraceEvent>>, event: impl FnOnce() -> TraceEvent) {
+    if let Some(events) = trace {
+        events.push(event());
+    }
+}
+
+/// Converts a fixed-point cost to the units reported to callers, where an orthogonal step on open ground costs 1.
+fn cost_units(cost: i64) -> f64 {
+    return cost as f64 / STRAIGHT_COST as f64;
+}
+
+/// An in-progress search that can be advanced one expansion at a time.
+/// Node identity is the node's index in the universe, so the state does not borrow the universe between steps.
+pub struct SearchState {
+    algorithm: PathFindingAlgorithm,
+    forward: SearchDirection,
+    backward: Option<SearchDirection>,
+    backward_turn: bool,
+    scanned: HashSet<usize>,
+    processed: Vec<GridNode>,
+    path: Vec<GridNode>,
+    trace: Option<Vec<TraceEvent>>,
+    peak_frontier_size: usize,
+    finished: bool,
+}
+
+impl SearchState {
+    pub fn new(
+        universe: &Universe,
+        start: usize,
+        end: usize,
+        algorithm: PathFindingAlgorithm,
+        trace: bool,
+    ) -> Result<Self, UniverseError> {
+        let start_node = universe.get_node_by_index(start);
+        let end_node = universe.get_node_by_index(end);
+
+        if !start_node.passable {
+            return Err(UniverseError::StartBlocked {
+                x: start_node.x,
+                y: start_node.y,
+            });
+        }
+
+        if !end_node.passable {
+            return Err(UniverseError::GoalBlocked {
+                x: end_node.x,
+                y: end_node.y,
+            });
+        }
+
+        if algorithm == PathFindingAlgorithm::JumpPointSearch && universe.has_weights() {
+            return Err(UniverseError::UnsupportedSearch(
+                "jump point search only supports unweighted grids".to_string(),
+            ));
+        }
+
+        let mut trace = if trace { Some(Vec::new()) } else { None };
+
+        let forward = SearchDirection::new(
+            algorithm,
+            TraceDirection::Forward,
+            universe,
+            start,
+            end,
+            &mut trace,
+        );
+
+        let backward = match algorithm {
+            PathFindingAlgorithm::AstarBidirectional | PathFindingAlgorithm::BFSBidirectional => {
+                Some(SearchDirection::new(
+                    algorithm,
+                    TraceDirection::Backward,
+                    universe,
+                    end,
+                    start,
+                    &mut trace,
+                ))
+            }
+            _ => None,
+        };
+
+        let mut search = SearchState {
+            algorithm,
+            forward,
+            backward,
+            backward_turn: false,
+            scanned: HashSet::new(),
+            processed: Vec::new(),
+            path: Vec::new(),
+            trace,
+            peak_frontier_size: 0,
+            finished: false,
+        };
+
+        search.peak_frontier_size = search.frontier_size();
+
+        return Ok(search);
+    }
+
+    pub fn is_finished(&self) -> bool {
+        return self.finished;
+    }
+
+    pub fn processed(&self) -> &[GridNode] {
+        return &self.processed;
+    }
+
+    pub fn path(&self) -> &[GridNode] {
+        return &self.path;
+    }
+
+    /// Nodes currently waiting to be expanded, in either direction, without duplicates.
+    pub fn frontier(&self, universe: &Universe) -> Vec<GridNode> {
+        let mut seen: HashSet<usize> = HashSet::new();
+        let mut indices = self.forward.frontier.indices(&self.forward.g_score);
+
+        if let Some(backward) = &self.backward {
+            indices.extend(backward.frontier.indices(&backward.g_score));
+        }
+
+        return indices
+            .into_iter()
+            .filter(|index| seen.insert(*index))
+            .map(|index| *universe.get_node_by_index(index))
+            .collect();
+    }
+
+    pub fn statistics(&self, universe: &Universe) -> SearchStatistics {
+        let path_cost: i64 = self
+            .path
+            .windows(2)
+            .map(|pair| universe.get_move_cost(&pair[0], &pair[1]))
+            .sum();
+
+        let mut statistics = SearchStatistics {
+            path_cost: cost_units(path_cost),
+            path_length: self.path.len().saturating_sub(1),
+            peak_frontier_size: self.peak_frontier_size,
+            ..SearchStatistics::default()
+        };
+
+        for direction in std::iter::once(&self.forward).chain(self.backward.as_ref()) {
+            statistics.nodes_expanded += direction.expanded.len() + direction.re_expansions;
+            statistics.nodes_generated += direction.generated;
+            statistics.re_expansions += direction.re_expansions;
+        }
+
+        return statistics;
+    }
+
+    pub fn into_result(self, universe: &Universe) -> PathResult {
+        let statistics = self.statistics(universe);
+
+        return PathResult {
+            path: self.path,
+            processed: self.processed,
+            statistics,
+            trace: self.trace,
+        };
+    }
+
+    /// Runs the search until it finds a path or exhausts every frontier.
+    pub fn run(&mut self, universe: &Universe) {
+        while !self.finished {
+            self.step(universe);
+        }
+    }
+
+    /// Expands a single node. Bidirectional searches alternate between their two directions.
+    pub fn step(&mut self, universe: &Universe) {
+        if self.finished {
+            return;
+        }
+
+        if self.frontiers_empty() {
+            self.finished = true;
+            return;
+        }
+
+        let SearchState {
+            algorithm,
+            forward,
+            backward,
+            backward_turn,
+            scanned,
+            processed,
+            trace,
+            ..
+        } = self;
+
+        let algorithm = *algorithm;
+        let is_backward = match backward {
+            Some(backward) => {
+                !backward.frontier.is_empty() && (*backward_turn || forward.frontier.is_empty())
+            }
+            None => false,
+        };
+
+        if backward.is_some() {
+            *backward_turn = !is_backward;
+        }
+
+        let (direction, other) = match backward {
+            Some(backward) if is_backward => (backward, Some(&*forward)),
+            _ => (forward, backward.as_ref()),
+        };
+
+        let current = direction.frontier.pop().unwrap();
+        let current_node = *universe.get_node_by_index(current);
+
+        // Jump point search already recorded the jump points it queued while scanning.
+        if !scanned.contains(&current) {
+            processed.push(current_node);
+        }
+
+        if !direction.expanded.insert(current) {
+            direction.re_expansions += 1;
+        }
+
+        record(trace, || {
+            let (g, h, f) = direction.scores(universe, current);
+
+            TraceEvent::Dequeue {
+                node: current_node,
+                direction: direction.kind,
+                g: g.map(cost_units),
+                h: h.map(cost_units),
+                f: f.map(cost_units),
+            }
+        });
+
+        if algorithm == PathFindingAlgorithm::DFS {
+            direction.visited.insert(current);
+        }
+
+        record(trace, || TraceEvent::Close {
+            node: current_node,
+            direction: direction.kind,
+        });
+
+        let mut found_path: Option<Vec<usize>> = None;
+
+        if current == direction.target {
+            let mut path = direction.chain(current);
+
+            if !is_backward {
+                path.reverse();
+            }
+
+            found_path = Some(path);
+        } else if let Some(other) = other {
+            if other.came_from.contains_key(&current) {
+                let (forward, backward) = if is_backward {
+                    (other, &*direction)
+                } else {
+                    (&*direction, other)
+                };
+
+                record(trace, || TraceEvent::Meet {
+                    node: current_node,
+                    forward_g: forward.g_score.get(&current).copied().map(cost_units),
+                    backward_g: backward.g_score.get(&current).copied().map(cost_units),
+                });
+
+                let mut path = forward.chain(current);
+                path.reverse();
+                path.extend(backward.chain(current).into_iter().skip(1));
+
+                found_path = Some(path);
+            }
+        }
+
+        if let Some(path) = found_path {
+            self.finish(universe, &path);
+            return;
+        }
+
+        match algorithm {
+            PathFindingAlgorithm::BFS
+            | PathFindingAlgorithm::BFSBidirectional
+            | PathFindingAlgorithm::GreedyBFS => {
+                for neighbor in universe.get_neighbor_indices(current) {
+                    if direction.visited.insert(neighbor) {
+                        let g_score = direction.g_score[&current]
+                            + universe
+                                .get_move_cost(&current_node, universe.get_node_by_index(neighbor));
+
+                        direction.g_score.insert(neighbor, g_score);
+                        direction.came_from.insert(neighbor, current);
+                        direction.enqueue(universe, neighbor, trace);
+                    }
+                }
+            }
+            PathFindingAlgorithm::DFS => {
+                for neighbor in universe.get_neighbor_indices(current) {
+                    if !direction.visited.contains(&neighbor) {
+                        let g_score = direction.g_score[&current]
+                            + universe
+                                .get_move_cost(&current_node, universe.get_node_by_index(neighbor));
+
+                        direction.g_score.insert(neighbor, g_score);
+                        direction.came_from.insert(neighbor, current);
+                        direction.enqueue(universe, neighbor, trace);
+                    }
+                }
+            }
+            PathFindingAlgorithm::Dijkstra
+            | PathFindingAlgorithm::Astar
+            | PathFindingAlgorithm::AstarBidirectional => {
+                for neighbor in universe.get_neighbor_indices(current) {
+                    let tentative_g_score = direction.g_score[&current]
+                        + universe
+                            .get_move_cost(&current_node, universe.get_node_by_index(neighbor));
+
+                    direction.relax(universe, neighbor, current, tentative_g_score, trace);
+                }
+            }
+            PathFindingAlgorithm::JumpPointSearch => {
+                SearchState::expand_jump_point(
+                    universe, direction, current, scanned, processed, trace,
+                );
+            }
+        }
+
+        direction.discard_stale();
+
+        self.peak_frontier_size = self.peak_frontier_size.max(self.frontier_size());
+
+        if self.frontiers_empty() {
+            self.finished = true;
+        }
+    }
+
+    fn frontier_size(&self) -> usize {
+        return self.forward.frontier.len()
+            + self
+                .backward
+                .as_ref()
+                .map_or(0, |backward| backward.frontier.len());
+    }
+
+    fn frontiers_empty(&self) -> bool {
+        return self.forward.frontier.is_empty()
+            && self
+                .backward
+                .as_ref()
+                .is_none_or(|backward| backward.frontier.is_empty());
+    }
+
+    fn finish(&mut self, universe: &Universe, path: &[usize]) {
+        let mut nodes: Vec<GridNode> = path
+            .iter()
+            .map(|index| *universe.get_node_by_index(*index))
+            .collect();
+
+        if self.algorithm == PathFindingAlgorithm::JumpPointSearch {
+            nodes = SearchState::expand_jump_points(universe, &nodes);
+        }
+
+        self.path = nodes;
+        self.finished = true;
+    }
+
+    fn expand_jump_point(
+        universe: &Universe,
+        direction: &mut SearchDirection,
+        current: usize,
+        scanned: &mut HashSet<usize>,
+        processed: &mut Vec<GridNode>,
+        trace: &mut Option<Vec<TraceEvent>>,
+    ) {
+        let current_node = universe.get_node_by_index(current);
+        let parent = direction
+            .came_from
+            .get(&current)
+            .map(|parent| universe.get_node_by_index(*parent));
+
+        for next in SearchState::get_jump_neighbors(universe, current_node, parent) {
+            let jump_point = SearchState::jump(
+                universe,
+                next.x,
+                next.y,
+                next.x - current_node.x,
+                next.y - current_node.y,
+                direction.target,
+                scanned,
+                processed,
+            );
+
+            if let Some(jump_point) = jump_point {
+                let tentative_g_score = direction.g_score[&current]
+                    + Universe::octile_distance(
+                        current_node,
+                        universe.get_node_by_index(jump_point),
+                    );
+
+                direction.relax(universe, jump_point, current, tentative_g_score, trace);
+            }
+        }
+    }
+
+    /// Scans from (x, y) in one direction until a jump point, the target or an obstacle is hit.
+    /// Every scanned cell is recorded in `processed` once so the visualizer can show the scan.
+    #[allow(clippy::too_many_arguments)]
+    fn jump(
+        universe: &Universe,
+        x: i32,
+        y: i32,
+        direction_x: i32,
+        direction_y: i32,
+        target: usize,
+        scanned: &mut HashSet<usize>,
+        processed: &mut Vec<GridNode>,
+    ) -> Option<usize> {
+        let mut x = x;
+        let mut y = y;
+
+        loop {
+            if !universe.is_passable(x, y) {
+                return None;
+            }
+
+            let index = universe.get_index(x, y);
+
+            if scanned.insert(index) {
+                processed.push(*universe.get_node_by_index(index));
+            }
+
+            if index == target
+                || SearchState::has_forced_neighbor(universe, x, y, direction_x, direction_y)
+            {
+                return Some(index);
+            }
+
+            // Four-way vertical scans and eight-way diagonal scans stop wherever one of their
+            // straight sub-scans would find a jump point.
+            let sub_scans: Vec<(i32, i32)> = match universe.connectivity {
+                Connectivity::FourWay if direction_y != 0 => vec![(-1, 0), (1, 0)],
+                Connectivity::EightWay | Connectivity::EightWayCornerCutting
+                    if direction_x != 0 && direction_y != 0 =>
+                {
+                    vec![(direction_x, 0), (0, direction_y)]
+                }
+                _ => Vec::new(),
+            };
+
+            for (sub_x, sub_y) in sub_scans {
+                let sub_jump = SearchState::jump(
+                    universe,
+                    x + sub_x,
+                    y + sub_y,
+                    sub_x,
+                    sub_y,
+                    target,
+                    scanned,
+                    processed,
+                );
+
+                if sub_jump.is_some() {
+                    return Some(index);
+                }
+            }
+
+            if !universe.can_step(x, y, direction_x, direction_y) {
+                return None;
+            }
+
+            x += direction_x;
+            y += direction_y;
+        }
+    }
+
+    fn has_forced_neighbor(
+        universe: &Universe,
+        x: i32,
+        y: i32,
+        direction_x: i32,
+        direction_y: i32,
+    ) -> bool {
+        let open = |x: i32, y: i32| universe.is_passable(x, y);
+        let (dx, dy) = (direction_x, direction_y);
+
+        return match universe.connectivity {
+            Connectivity::FourWay | Connectivity::EightWay => {
+                if dx != 0 && dy != 0 {
+                    false
+                } else if dx != 0 {
+                    (open(x, y - 1) && !open(x - dx, y - 1))
+                        || (open(x, y + 1) && !open(x - dx, y + 1))
+                } else {
+                    (open(x - 1, y) && !open(x - 1, y - dy))
+                        || (open(x + 1, y) && !open(x + 1, y - dy))
+                }
+            }
+            Connectivity::EightWayCornerCutting => {
+                if dx != 0 && dy != 0 {
+                    (open(x - dx, y + dy) && !open(x - dx, y))
+                        || (open(x + dx, y - dy) && !open(x, y - dy))
+                } else if dx != 0 {
+                    (open(x + dx, y + 1) && !open(x, y + 1))
+                        || (open(x + dx, y - 1) && !open(x, y - 1))
+                } else {
+                    (open(x + 1, y + dy) && !open(x + 1, y))
+                        || (open(x - 1, y + dy) && !open(x - 1, y))
+                }
+            }
+        };
+    }
+
+    fn get_jump_neighbors<'a>(
+        universe: &'a Universe,
+        node: &GridNode,
+        parent: Option<&GridNode>,
+    ) -> Vec<&'a GridNode> {
+        let parent = match parent {
+            Some(parent) => parent,
+            None => return universe.get_neighbors(node.x, node.y),
+        };
+
+        let open = |x: i32, y: i32| universe.is_passable(x, y);
+        let (x, y) = (node.x, node.y);
+        let dx = (node.x - parent.x).signum();
+        let dy = (node.y - parent.y).signum();
+
+        let mut directions: Vec<(i32, i32)> = Vec::with_capacity(5);
+
+        if dx != 0 && dy != 0 {
+            directions.extend([(dx, dy), (dx, 0), (0, dy)]);
+
+            if universe.connectivity == Connectivity::EightWayCornerCutting {
+                if !open(x - dx, y) {
+                    directions.push((-dx, dy));
+                }
+
+                if !open(x, y - dy) {
+                    directions.push((dx, -dy));
+                }
+            }
+        } else {
+            // (side_x, side_y) is perpendicular to the straight direction of travel.
+            let (side_x, side_y) = (dy.abs(), dx.abs());
+
+            directions.push((dx, dy));
+
+            match universe.connectivity {
+                Connectivity::FourWay => {
+                    directions.extend([(side_x, side_y), (-side_x, -side_y)]);
+                }
+                Connectivity::EightWay => {
+                    directions.extend([
+                        (side_x, side_y),
+                        (-side_x, -side_y),
+                        (dx + side_x, dy + side_y),
+                        (dx - side_x, dy - side_y),
+                    ]);
+                }
+                Connectivity::EightWayCornerCutting => {
+                    for side in [1, -1] {
+                        if !open(x + side_x * side, y + side_y * side) {
+                            directions.push((dx + side_x * side, dy + side_y * side));
+                        }
+                    }
+                }
+            }
+        }
+
+        return directions
+            .into_iter()
+            .filter(|(step_x, step_y)| universe.can_step(x, y, *step_x, *step_y))
+            .map(|(step_x, step_y)| universe.get_node_ref(x + step_x, y + step_y))
+            .collect();
+    }
+
+    fn expand_jump_points(universe: &Universe, jump_points: &[GridNode]) -> Vec<GridNode> {
+        let mut path: Vec<GridNode> = Vec::new();
+
+        for pair in jump_points.windows(2) {
+            let (from, to) = (pair[0], pair[1]);
+            let direction_x = (to.x - from.x).signum();
+            let direction_y = (to.y - from.y).signum();
+
+            let mut x = from.x;
+            let mut y = from.y;
+
+            while x != to.x || y != to.y {
+                path.push(*universe.get_node_ref(x, y));
+                x += direction_x;
+                y += direction_y;
+            }
+        }
+
+        if let Some(last) = jump_points.last() {
+            path.push(*last);
+        }
+
+        return path;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::universe::Heuristic;
+
+    fn run(
+        universe: &Universe,
+        start: (i32, i32),
+        end: (i32, i32),
+        algorithm: PathFindingAlgorithm,
+    ) -> PathResult {
+        let start = universe.get_index(start.0, start.1);
+        let end = universe.get_index(end.0, end.1);
+        let mut search = SearchState::new(universe, start, end, algorithm, true).unwrap();
+        search.run(universe);
+
+        return search.into_result(universe);
+    }
+
+    #[test]
+    fn trace_is_only_recorded_when_requested() {
+        let universe = Universe::new(4, 4);
+        let mut search =
+            SearchState::new(&universe, 0, 15, PathFindingAlgorithm::Astar, false).unwrap();
+        search.run(&universe);
+
+        assert!(search.into_result(&universe).trace.is_none());
+    }
+
+    #[test]
+    fn astar_trace_carries_scores() {
+        let universe = Universe::new(4, 4);
+        let result = run(&universe, (0, 0), (3, 3), PathFindingAlgorithm::Astar);
+        let trace = result.trace.unwrap();
+
+        assert_eq!(
+            trace[0],
+            TraceEvent::Enqueue {
+                node: universe.get_node(0, 0).unwrap(),
+                direction: TraceDirection::Forward,
+                priority: Some(6.0),
+                g: Some(0.0),
+                h: Some(6.0),
+                f: Some(6.0),
+            }
+        );
+        assert!(matches!(trace[1], TraceEvent::Dequeue { g: Some(0.0), .. }));
+        assert!(matches!(trace[2], TraceEvent::Close { .. }));
+
+        let dequeues = trace
+            .iter()
+            .filter(|event| matches!(event, TraceEvent::Dequeue { .. }))
+            .count();
+
+        assert_eq!(dequeues, result.processed.len());
+
+        for event in &trace {
+            if let TraceEvent::Relax {
+                old_g, new_g, h, f, ..
+            } = event
+            {
+                assert!(old_g.is_none_or(|old_g| *new_g < old_g));
+                assert!((f.unwrap() - (h.unwrap() + new_g)).abs() < 1e-9);
+            }
+        }
+    }
+
+    /// A 20x20 eight-way grid with uneven weights, where lazy deletion leaves plenty of superseded queue entries.
+    fn weighted_grid() -> Universe {
+        let mut universe = Universe::new(20, 20);
+        universe.connectivity = Connectivity::EightWay;
+
+        for y in 0..20 {
+            for x in 0..20 {
+                universe
+                    .set_weight(x, y, (x * x + x * y + y * 5) % 11)
+                    .unwrap();
+            }
+        }
+
+        return universe;
+    }
+
+    #[test]
+    fn consistent_astar_never_re_expands() {
+        let universe = weighted_grid();
+        let result = run(&universe, (0, 10), (19, 10), PathFindingAlgorithm::Astar);
+        let unique: HashSet<&GridNode> = result.processed.iter().collect();
+
+        assert_eq!(result.statistics.re_expansions, 0);
+        assert_eq!(result.statistics.nodes_expanded, unique.len());
+        assert_eq!(result.processed.len(), unique.len());
+    }
+
+    #[test]
+    fn frontier_leaves_out_expanded_nodes() {
+        let universe = weighted_grid();
+        let start = universe.get_index(0, 10);
+        let end = universe.get_index(19, 10);
+        let mut search =
+            SearchState::new(&universe, start, end, PathFindingAlgorithm::Astar, false).unwrap();
+
+        while !search.is_finished() {
+            search.step(&universe);
+
+            let expanded: HashSet<&GridNode> = search.processed().iter().collect();
+            assert!(search
+                .frontier(&universe)
+                .iter()
+                .all(|node| !expanded.contains(node)));
+        }
+    }
+
+    #[test]
+    fn relax_records_improved_costs() {
+        let mut universe = Universe::new(10, 10);
+        universe.connectivity = Connectivity::EightWay;
+        universe.set_heuristic(Some(Heuristic::Manhattan));
+        universe.heuristic_weight = 3.0;
+
+        for y in 0..8 {
+            universe.set_passable(5, y, false).unwrap();
+        }
+
+        let trace = run(&universe, (0, 0), (9, 0), PathFindingAlgorithm::Astar)
+            .trace
+            .unwrap();
+
+        assert!(trace
+            .iter()
+            .any(|event| matches!(event, TraceEvent::Relax { old_g: Some(_), .. })));
+    }
+
+    #[test]
+    fn bidirectional_trace_records_meet_point() {
+        let universe = Universe::new(9, 3);
+
+        for algorithm in [
+            PathFindingAlgorithm::BFSBidirectional,
+            PathFindingAlgorithm::AstarBidirectional,
+        ] {
+            let result = run(&universe, (0, 1), (8, 1), algorithm);
+            let trace = result.trace.unwrap();
+
+            assert!(trace.iter().any(|event| matches!(
+                event,
+                TraceEvent::Dequeue {
+                    direction: TraceDirection::Backward,
+                    ..
+                }
+            )));
+
+            match trace.last() {
+                Some(TraceEvent::Meet {
+                    forward_g: Some(forward_g),
+                    backward_g: Some(backward_g),
+                    ..
+                }) => assert_eq!(forward_g + backward_g, (result.path.len() - 1) as f64),
+                event => panic!("expected a meet event, got {:?}", event),
+            }
+        }
+    }
+}
@@ -0,0 +1,19 @@
+use serde::Serialize;
+
+/// Summary of how much work a search did and what the path it found costs.
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchStatistics {
+    /// Sum of step costs (1 per orthogonal step, √2 per diagonal step) plus the weight of every node stepped onto.
+    pub path_cost: f64,
+    /// Number of steps in the path, which is one less than the number of nodes in it.
+    pub path_length: usize,
+    /// Number of nodes taken off a frontier, including re-expansions.
+    pub nodes_expanded: usize,
+    /// Number of nodes put onto a frontier, including the start (and the end for bidirectional searches).
+    pub nodes_generated: usize,
+    /// Largest number of entries waiting on the frontiers at once.
+    pub peak_frontier_size: usize,
+    /// Number of times a node was taken off a frontier after it had already been expanded.
+    pub re_expansions: usize,
+}
@@ -0,0 +1,11 @@
+use serde::Serialize;
+
+use super::GridNode;
+
+#[derive(Serialize, Debug)]
+pub struct SearchStep {
+    pub processed: Vec<GridNode>,
+    pub frontier: Vec<GridNode>,
+    pub path: Vec<GridNode>,
+    pub finished: bool,
+}
@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+
+use super::UniverseError;
+
+/// Shape of the noise `generate_terrain` samples, and the range its weights are scaled into.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TerrainOptions {
+    /// Number of noise layers summed together. More octaves add finer detail.
+    pub octaves: u32,
+    /// Width in nodes of the broadest hills.
+    pub scale: f64,
+    /// How much each octave contributes relative to the one before it.
+    pub persistence: f64,
+    pub min_weight: i32,
+    pub max_weight: i32,
+}
+
+impl Default for TerrainOptions {
+    fn default() -> Self {
+        return TerrainOptions {
+            octaves: 4,
+            scale: 16.0,
+            persistence: 0.5,
+            min_weight: 0,
+            max_weight: 10,
+        };
+    }
+}
+
+impl TerrainOptions {
+    pub(super) fn validate(&self) -> Result<(), UniverseError> {
+        if self.octaves == 0 || self.octaves > 16 {
+            return Err(UniverseError::InvalidOptions(format!(
+                "terrain octaves must be between 1 and 16, got {}",
+                self.octaves
+            )));
+        }
+
+        if self.scale <= 0.0 || !self.scale.is_finite() {
+            return Err(UniverseError::InvalidOptions(format!(
+                "terrain scale must be positive, got {}",
+                self.scale
+            )));
+        }
+
+        if self.persistence <= 0.0 || !self.persistence.is_finite() {
+            return Err(UniverseError::InvalidOptions(format!(
+                "terrain persistence must be positive, got {}",
+                self.persistence
+            )));
+        }
+
+        if self.min_weight < 0 || self.max_weight < self.min_weight {
+            return Err(UniverseError::InvalidOptions(format!(
+                "invalid terrain weight range {}..{}",
+                self.min_weight, self.max_weight
+            )));
+        }
+
+        return Ok(());
+    }
+}
@@ -0,0 +1,58 @@
+use serde::Serialize;
+
+use super::GridNode;
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum TraceDirection {
+    /// Searching from the start towards the end.
+    Forward,
+    /// Searching from the end towards the start. Only bidirectional algorithms have this direction.
+    Backward,
+}
+
+/// A single operation a search performed, recorded in order when tracing is enabled.
+/// `g` is the cost from the direction's root, `h` the heuristic estimate to its target and `f` the
+/// value the frontier is ordered by. Each is only present for algorithms that track it, and all are in the same
+/// units as `SearchStatistics::path_cost`.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum TraceEvent {
+    Enqueue {
+        node: GridNode,
+        direction: TraceDirection,
+        /// Lower priorities are dequeued first. Absent for plain queues and stacks.
+        priority: Option<f64>,
+        g: Option<f64>,
+        h: Option<f64>,
+        f: Option<f64>,
+    },
+    Dequeue {
+        node: GridNode,
+        direction: TraceDirection,
+        g: Option<f64>,
+        h: Option<f64>,
+        f: Option<f64>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Relax {
+        node: GridNode,
+        from: GridNode,
+        direction: TraceDirection,
+        /// Absent the first time a node is reached.
+        old_g: Option<f64>,
+        new_g: f64,
+        h: Option<f64>,
+        f: Option<f64>,
+    },
+    Close {
+        node: GridNode,
+        direction: TraceDirection,
+    },
+    #[serde(rename_all = "camelCase")]
+    Meet {
+        node: GridNode,
+        forward_g: Option<f64>,
+        backward_g: Option<f64>,
+    },
+}
@@ -1,21 +1,38 @@
-use rand::Rng;
-use std::collections::{HashMap, HashSet, VecDeque};
-use wasm_bindgen::{prelude::wasm_bindgen, JsCast, JsValue};
-
-use crate::{IGridSnapshot, IMazeResponse, IPathResponse, PriorityQueue};
-
-use super::{GridNode, GridSnapshot, MazeType, Orientation, PathFindingAlgorithm, PathResult};
-
-#[wasm_bindgen]
+use std::collections::VecDeque;
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::UnionFind;
+
+use super::{
+    ascii,
+    grid_snapshot::{LEGACY_SNAPSHOT_VERSION, SNAPSHOT_VERSION},
+    maze_lattice::{random_index, shuffle},
+    moving_ai, AsciiGrid, AsciiOverlay, Connectivity, GridNode, GridSnapshot, Heuristic,
+    MazeConstruction, MazeEvent, MazeLattice, MazeOptions, MazeType, Orientation,
+    PathFindingAlgorithm, PathResult, Point, SearchState, TerrainOptions, UniverseError,
+    ValueNoise, DIAGONAL_COST, STRAIGHT_COST,
+};
+
+/// The most cells a grid read from a snapshot or map file may have, so a corrupt header cannot exhaust memory.
+pub const MAX_GRID_CELLS: u32 = 1 << 24;
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone)]
 pub struct Universe {
     pub width: u32,
     pub height: u32,
+    pub connectivity: Connectivity,
+    /// Multiplier applied to every heuristic estimate. Values above 1 trade optimality for fewer expansions.
+    pub heuristic_weight: f64,
+    heuristic: Option<Heuristic>,
     nodes: Vec<GridNode>,
 }
 
-#[wasm_bindgen]
 impl Universe {
-    #[wasm_bindgen(constructor)]
     pub fn new(width: u32, height: u32) -> Self {
         let mut nodes: Vec<GridNode> = Vec::with_capacity((width * height) as usize);
 
@@ -33,6 +50,9 @@ impl Universe {
         return Universe {
             width,
             height,
+            connectivity: Connectivity::FourWay,
+            heuristic_weight: 1.0,
+            heuristic: None,
             nodes,
         };
     }
@@ -63,13 +83,11 @@ impl Universe {
         }
     }
 
-    #[wasm_bindgen(js_name = getNode)]
-    pub fn get_node(&self, x: i32, y: i32) -> GridNode {
-        let index = self.get_index(x, y);
-        return self.nodes[index];
+    pub fn get_node(&self, x: i32, y: i32) -> Result<GridNode, UniverseError> {
+        let index = self.check_bounds(x, y)?;
+        return Ok(self.nodes[index]);
     }
 
-    #[wasm_bindgen(js_name = hasNode)]
     pub fn has_node(&self, x: i32, y: i32) -> bool {
         if x < 0 || x as u32 >= self.width || y < 0 || y as u32 >= self.height {
             return false;
@@ -78,28 +96,37 @@ impl Universe {
         return true;
     }
 
-    #[wasm_bindgen(js_name = generateMaze)]
-    pub fn generate_maze(&self, maze_type: MazeType) -> IMazeResponse {
-        let mut maze: Vec<GridNode> = Vec::new();
-        match maze_type {
-            MazeType::Random25 => self.random_nodes(&mut maze, 25),
-            MazeType::Random50 => self.random_nodes(&mut maze, 50),
-            MazeType::Random75 => self.random_nodes(&mut maze, 75),
-            MazeType::RecursiveDivision => self.recursive_division_maze(
-                &mut maze,
-                2,
-                self.height as i32 - 3,
-                2,
-                self.width as i32 - 3,
-                Orientation::Horizontal,
-                true,
-            ),
+    pub fn set_weight(&mut self, x: i32, y: i32, weight: i32) -> Result<(), UniverseError> {
+        let index = self.check_bounds(x, y)?;
+        self.nodes[index].weight = weight;
+
+        return Ok(());
+    }
+
+    pub fn set_passable(&mut self, x: i32, y: i32, passable: bool) -> Result<(), UniverseError> {
+        let index = self.check_bounds(x, y)?;
+        self.nodes[index].passable = passable;
+
+        return Ok(());
+    }
+
+    /// Returns the heuristic searches will use, which follows the connectivity unless one was set explicitly.
+    pub fn get_heuristic(&self) -> Heuristic {
+        return match (self.heuristic, self.connectivity) {
+            (Some(heuristic), _) => heuristic,
+            (None, Connectivity::FourWay) => Heuristic::Manhattan,
+            (None, Connectivity::EightWay | Connectivity::EightWayCornerCutting) => {
+                Heuristic::Octile
+            }
         };
+    }
 
-        return JsValue::from_serde(&maze).unwrap().unchecked_into();
+    /// Sets the heuristic searches will use. Passing `None` restores the connectivity-based default.
+    pub fn set_heuristic(&mut self, heuristic: Option<Heuristic>) {
+        self.heuristic = heuristic;
     }
 
-    #[wasm_bindgen(js_name = findPath)]
+    /// Searches from start to end, recording every frontier change when `trace` is set.
     pub fn find_path(
         &self,
         start_x: i32,
@@ -107,53 +134,118 @@ impl Universe {
         end_x: i32,
         end_y: i32,
         algorithm: PathFindingAlgorithm,
-    ) -> IPathResponse {
-        let path = match algorithm {
-            PathFindingAlgorithm::Dijkstra => self.dijkstra(start_x, start_y, end_x, end_y),
-            PathFindingAlgorithm::Astar => self.astar(start_x, start_y, end_x, end_y),
-            PathFindingAlgorithm::AstarBidirectional => {
-                self.astar_bidirectional(start_x, start_y, end_x, end_y)
-            }
-            PathFindingAlgorithm::BFS => self.bfs(start_x, start_y, end_x, end_y),
-            PathFindingAlgorithm::BFSBidirectional => {
-                self.bfs_bidirectional(start_x, start_y, end_x, end_y)
-            }
-            PathFindingAlgorithm::DFS => self.dfs(start_x, start_y, end_x, end_y),
-            PathFindingAlgorithm::GreedyBFS => self.greedy_bfs(start_x, start_y, end_x, end_y),
-        };
+        trace: bool,
+    ) -> Result<PathResult, UniverseError> {
+        let start = self.check_bounds(start_x, start_y)?;
+        let end = self.check_bounds(end_x, end_y)?;
 
-        return JsValue::from_serde(&path).unwrap().unchecked_into();
+        let mut search = SearchState::new(self, start, end, algorithm, trace)?;
+        search.run(self);
+
+        return Ok(search.into_result(self));
     }
 
-    #[wasm_bindgen(js_name = setWeight)]
-    pub fn set_weight(&mut self, x: i32, y: i32, weight: i32) {
-        let index = self.get_index(x, y);
-        self.nodes[index].weight = weight;
+    /// Generates a maze of the given type. Passing a seed makes the maze reproducible on any platform.
+    /// The nodes returned are walls (not passable), followed by any passable nodes the maze gives a weight.
+    pub fn generate_maze(
+        &self,
+        maze_type: MazeType,
+        seed: Option<u32>,
+        options: &MazeOptions,
+    ) -> Result<Vec<GridNode>, UniverseError> {
+        return self.build_maze(maze_type, seed, options, &mut None);
     }
 
-    #[wasm_bindgen(js_name = setPassable)]
-    pub fn set_passable(&mut self, x: i32, y: i32, passable: bool) {
-        let index = self.get_index(x, y);
-        self.nodes[index].passable = passable;
+    /// Generates a maze along with the ordered changes that built it.
+    pub fn generate_maze_construction(
+        &self,
+        maze_type: MazeType,
+        seed: Option<u32>,
+        options: &MazeOptions,
+    ) -> Result<MazeConstruction, UniverseError> {
+        let mut events = Some(Vec::new());
+        let maze = self.build_maze(maze_type, seed, options, &mut events)?;
+
+        return Ok(MazeConstruction {
+            maze,
+            starts_filled: maze_type.starts_filled(),
+            events: events.unwrap_or_default(),
+        });
     }
 
-    #[wasm_bindgen(js_name = getSnapshot)]
-    pub fn get_snapshot(&self) -> IGridSnapshot {
-        let snapshot = self.get_snapshot_prv();
-        return JsValue::from_serde(&snapshot).unwrap().unchecked_into();
+    /// Generates a maze and applies it to the grid in place, returning only the nodes whose state changed.
+    pub fn apply_maze(
+        &mut self,
+        maze_type: MazeType,
+        seed: Option<u32>,
+        options: &MazeOptions,
+    ) -> Result<Vec<GridNode>, UniverseError> {
+        let maze = self.generate_maze(maze_type, seed, options)?;
+        let previous = self.nodes.clone();
+
+        if options.clear {
+            self.reset();
+        }
+
+        for node in maze {
+            let index = self.get_index(node.x, node.y);
+
+            // Walls carry no weight, the same as walls drawn by hand.
+            self.nodes[index].weight = if node.passable { node.weight } else { 0 };
+            self.nodes[index].passable = node.passable;
+        }
+
+        return Ok(self
+            .nodes
+            .iter()
+            .zip(previous)
+            .filter(|(node, previous)| **node != *previous)
+            .map(|(node, _)| *node)
+            .collect());
     }
-}
 
-impl Universe {
-    fn get_index(&self, x: i32, y: i32) -> usize {
-        (y * self.width as i32 + x) as usize
+    /// Gives every node a weight from coherent noise. Walls are returned unchanged.
+    pub fn generate_terrain(
+        &self,
+        seed: Option<u32>,
+        options: &TerrainOptions,
+    ) -> Result<Vec<GridNode>, UniverseError> {
+        options.validate()?;
+
+        let seed = match seed {
+            Some(seed) => seed,
+            None => ChaCha8Rng::from_entropy().gen(),
+        };
+        let noise = ValueNoise::new(seed, options.octaves, options.persistence);
+        let weight_range = (options.max_weight - options.min_weight) as f64;
+
+        return Ok(self
+            .nodes
+            .iter()
+            .map(|node| {
+                if !node.passable {
+                    return *node;
+                }
+
+                let height = noise.sample(
+                    (node.x as f64 + 0.5) / options.scale,
+                    (node.y as f64 + 0.5) / options.scale,
+                );
+
+                return GridNode {
+                    weight: options.min_weight + (height * weight_range).round() as i32,
+                    ..*node
+                };
+            })
+            .collect());
     }
 
-    fn get_snapshot_prv(&self) -> GridSnapshot {
+    pub fn get_snapshot(&self) -> GridSnapshot {
         let mut snapshot = GridSnapshot {
+            version: SNAPSHOT_VERSION,
             width: self.width as i32,
             height: self.height as i32,
-            weight: 0,
+            weight: None,
             walls: Vec::new(),
             weights: Vec::new(),
         };
@@ -162,680 +254,2369 @@ impl Universe {
             if !node.passable {
                 snapshot.walls.push(format!("{},{}", node.x, node.y));
             } else if node.weight > 0 {
-                snapshot.weights.push(format!("{},{}", node.x, node.y));
-                snapshot.weight = node.weight;
+                snapshot
+                    .weights
+                    .push(format!("{},{},{}", node.x, node.y, node.weight));
             }
         }
 
         return snapshot;
     }
 
-    fn get_node_ref(&self, x: i32, y: i32) -> &GridNode {
-        let index = self.get_index(x, y);
-        return &self.nodes[index];
-    }
-
-    fn random_nodes<'a>(&self, result: &'a mut Vec<GridNode>, percentage: i32) {
-        let mut rng = rand::thread_rng();
+    /// Replaces the grid with the one described by the snapshot, resizing it to the snapshot's dimensions.
+    /// The grid is left untouched if the snapshot is malformed or references cells outside its bounds.
+    pub fn load_snapshot(&mut self, snapshot: &GridSnapshot) -> Result<(), UniverseError> {
+        if snapshot.version != LEGACY_SNAPSHOT_VERSION && snapshot.version != SNAPSHOT_VERSION {
+            return Err(UniverseError::InvalidSnapshot(format!(
+                "unsupported snapshot version {}",
+                snapshot.version
+            )));
+        }
 
-        for node in &self.nodes {
-            let rand = rng.gen_range(0..100);
+        let cells = match (
+            u32::try_from(snapshot.width),
+            u32::try_from(snapshot.height),
+        ) {
+            (Ok(width), Ok(height)) if width > 0 && height > 0 => {
+                Universe::cell_count(width, height)
+            }
+            _ => None,
+        };
 
-            if rand < percentage {
-                result.push(node.clone());
+        let cells = match cells {
+            Some(cells) => cells as usize,
+            None => {
+                return Err(UniverseError::InvalidSnapshot(format!(
+                    "invalid snapshot dimensions {}x{}",
+                    snapshot.width, snapshot.height
+                )))
             }
-        }
-    }
+        };
 
-    fn recursive_division_maze<'a>(
-        &self,
-        result: &'a mut Vec<GridNode>,
-        row_start: i32,
-        row_end: i32,
-        col_start: i32,
-        col_end: i32,
-        orientation: Orientation,
-        surrounding_walls: bool,
-    ) {
-        if row_end < row_start || col_end < col_start {
-            return;
+        if snapshot.walls.len() > cells || snapshot.weights.len() > cells {
+            return Err(UniverseError::InvalidSnapshot(format!(
+                "snapshot lists more cells than its {}x{} grid holds",
+                snapshot.width, snapshot.height
+            )));
         }
 
-        if surrounding_walls {
-            for row in 0..self.height as i32 {
-                let left_node = self.get_node(0, row).clone();
-                let right_node = self.get_node((self.width - 1) as i32, row).clone();
-
-                result.push(left_node);
-                result.push(right_node);
-            }
+        // Version 1 snapshots store bare "x,y" weight keys that all share the snapshot's weight.
+        let legacy_weight = match snapshot.version {
+            LEGACY_SNAPSHOT_VERSION => Some(snapshot.weight.ok_or_else(|| {
+                UniverseError::InvalidSnapshot(
+                    "version 1 snapshot is missing its weight".to_string(),
+                )
+            })?),
+            _ => None,
+        };
 
-            for col in 0..self.width as i32 {
-                let top_node = self.get_node(col, 0).clone();
-                let bottom_node = self.get_node(col, (self.height - 1) as i32).clone();
+        let mut universe = Universe::new(snapshot.width as u32, snapshot.height as u32);
 
-                result.push(top_node);
-                result.push(bottom_node);
-            }
+        for key in &snapshot.walls {
+            let (x, y, _) = universe.parse_snapshot_key(key, "wall", false)?;
+            universe.set_passable(x, y, false)?;
         }
 
-        let mut possible_rows: Vec<i32> = Vec::new();
-
-        for i in (if orientation == Orientation::Horizontal {
-            row_start
-        } else {
-            row_start - 1
-        }..if orientation == Orientation::Horizontal {
-            row_end + 1
-        } else {
-            row_end + 2
-        })
-            .step_by(2)
-        {
-            possible_rows.push(i);
+        for key in &snapshot.weights {
+            let (x, y, weight) = match legacy_weight {
+                Some(weight) => {
+                    let (x, y, _) = universe.parse_snapshot_key(key, "weight", false)?;
+                    (x, y, weight)
+                }
+                None => universe.parse_snapshot_key(key, "weight", true)?,
+            };
+            universe.set_weight(x, y, weight)?;
         }
 
-        let mut possible_cols: Vec<i32> = Vec::new();
-
-        for i in (if orientation == Orientation::Horizontal {
-            col_start - 1
-        } else {
-            col_start
-        }..if orientation == Orientation::Horizontal {
-            col_end + 2
-        } else {
-            col_end + 1
-        })
-            .step_by(2)
-        {
-            possible_cols.push(i);
-        }
+        self.width = universe.width;
+        self.height = universe.height;
+        self.nodes = universe.nodes;
 
-        let mut rng = rand::thread_rng();
+        return Ok(());
+    }
 
-        let rand_row_index = rng.gen_range(0..possible_rows.len());
-        let rand_col_index = rng.gen_range(0..possible_cols.len());
+    /// Builds a universe from a Moving AI benchmark `.map` file.
+    pub fn from_moving_ai_map(map: &str) -> Result<Universe, UniverseError> {
+        return moving_ai::parse_map(map);
+    }
 
-        let rand_row = possible_rows[rand_row_index];
-        let rand_col = possible_cols[rand_col_index];
+    /// Writes the grid as a Moving AI benchmark `.map` file. Weights are not part of the format and are dropped.
+    pub fn to_moving_ai_map(&self) -> String {
+        return moving_ai::write_map(self);
+    }
 
-        if orientation == Orientation::Horizontal {
-            for col in 0..self.width as i32 {
-                if col != rand_col && col >= col_start - 1 && col <= col_end + 1 {
-                    let node = self.get_node(col as i32, rand_row as i32).clone();
-                    result.push(node);
-                }
-            }
+    /// Reads a grid, and any start and end marked on it, from the text format described in `ascii`.
+    pub fn from_ascii(ascii: &str) -> Result<AsciiGrid, UniverseError> {
+        return ascii::parse_ascii(ascii);
+    }
 
-            if rand_row - 2 - row_start > col_end - col_start {
-                self.recursive_division_maze(
-                    result,
-                    row_start,
-                    rand_row - 2,
-                    col_start,
-                    col_end,
-                    orientation,
-                    false,
-                );
-            } else {
-                self.recursive_division_maze(
-                    result,
-                    row_start,
-                    rand_row - 2,
-                    col_start,
-                    col_end,
-                    Orientation::Vertical,
-                    false,
-                );
-            }
+    /// Writes the grid in the text format described in `ascii`.
+    pub fn to_ascii(&self) -> String {
+        return ascii::write_ascii(self, &AsciiOverlay::default());
+    }
 
-            if row_end - (rand_row + 2) > col_end - col_start {
-                self.recursive_division_maze(
-                    result,
-                    rand_row + 2,
-                    row_end,
-                    col_start,
-                    col_end,
-                    orientation,
-                    false,
-                );
-            } else {
-                self.recursive_division_maze(
-                    result,
-                    rand_row + 2,
-                    row_end,
-                    col_start,
-                    col_end,
-                    Orientation::Vertical,
-                    false,
-                );
-            }
-        } else {
-            for row in 0..self.height as i32 {
-                if row != rand_row && row >= row_start - 1 && row <= row_end + 1 {
-                    let node = self.get_node(rand_col as i32, row as i32).clone();
-                    result.push(node);
-                }
-            }
+    /// Writes the grid in the text format described in `ascii`, with endpoints and a search drawn over it.
+    pub fn to_ascii_with_overlay(&self, overlay: &AsciiOverlay) -> String {
+        return ascii::write_ascii(self, overlay);
+    }
+}
 
-            if row_end - row_start > rand_col - 2 - col_start {
-                self.recursive_division_maze(
-                    result,
-                    row_start,
-                    row_end,
-                    col_start,
-                    rand_col - 2,
-                    Orientation::Horizontal,
-                    false,
-                );
-            } else {
-                self.recursive_division_maze(
-                    result,
-                    row_start,
-                    row_end,
-                    col_start,
-                    rand_col - 2,
-                    orientation,
-                    false,
-                );
-            }
+impl Universe {
+    pub(super) fn get_index(&self, x: i32, y: i32) -> usize {
+        (y * self.width as i32 + x) as usize
+    }
 
-            if row_end - row_start > col_end - (rand_col + 2) {
-                self.recursive_division_maze(
-                    result,
-                    row_start,
-                    row_end,
-                    rand_col + 2,
-                    col_end,
-                    Orientation::Horizontal,
-                    false,
-                );
-            } else {
-                self.recursive_division_maze(
-                    result,
-                    row_start,
-                    row_end,
-                    rand_col + 2,
-                    col_end,
-                    orientation,
-                    false,
-                );
-            }
+    /// Returns the index of `x,y`, or an out of bounds error if it is not on the grid.
+    pub(super) fn check_bounds(&self, x: i32, y: i32) -> Result<usize, UniverseError> {
+        if !self.has_node(x, y) {
+            return Err(UniverseError::OutOfBounds {
+                x,
+                y,
+                width: self.width,
+                height: self.height,
+            });
         }
+
+        return Ok(self.get_index(x, y));
+    }
+
+    /// Returns the number of cells in a `width` by `height` grid, or `None` if it would exceed `MAX_GRID_CELLS`.
+    pub(super) fn cell_count(width: u32, height: u32) -> Option<u32> {
+        return width
+            .checked_mul(height)
+            .filter(|&cells| cells <= MAX_GRID_CELLS);
     }
 
-    fn bfs(&self, start_x: i32, start_y: i32, end_x: i32, end_y: i32) -> PathResult {
-        let mut result = PathResult {
-            path: Vec::new(),
-            processed: Vec::new(),
+    /// Parses an "x,y" key, or "x,y,weight" when `weighted` is set, checking that the cell is on the grid.
+    fn parse_snapshot_key(
+        &self,
+        key: &str,
+        kind: &str,
+        weighted: bool,
+    ) -> Result<(i32, i32, i32), UniverseError> {
+        let fields: Option<Vec<i32>> = key
+            .split(',')
+            .map(|field| field.trim().parse().ok())
+            .collect();
+
+        let (x, y, weight) = match (fields.as_deref(), weighted) {
+            (Some(&[x, y]), false) => (x, y, 0),
+            (Some(&[x, y, weight]), true) => (x, y, weight),
+            _ => {
+                return Err(UniverseError::InvalidSnapshot(format!(
+                    "malformed {} entry \"{}\"",
+                    kind, key
+                )))
+            }
         };
 
-        let mut frontier: VecDeque<&GridNode> = VecDeque::new();
+        if !self.has_node(x, y) {
+            return Err(UniverseError::InvalidSnapshot(format!(
+                "{} at {},{} is outside the {}x{} grid",
+                kind, x, y, self.width, self.height
+            )));
+        }
 
-        let start_node = self.get_node_ref(start_x, start_y);
-        let end_node = self.get_node_ref(end_x, end_y);
+        return Ok((x, y, weight));
+    }
 
-        let mut came_from: HashMap<&GridNode, &GridNode> = HashMap::new();
-        let mut visited: HashSet<&GridNode> = HashSet::new();
+    pub(super) fn get_node_ref(&self, x: i32, y: i32) -> &GridNode {
+        let index = self.get_index(x, y);
+        return &self.nodes[index];
+    }
 
-        frontier.push_back(start_node);
-        visited.insert(start_node);
+    pub(super) fn get_node_by_index(&self, index: usize) -> &GridNode {
+        return &self.nodes[index];
+    }
 
-        while frontier.len() > 0 {
-            let current = frontier.pop_front().unwrap();
+    pub(super) fn has_weights(&self) -> bool {
+        return self
+            .nodes
+            .iter()
+            .any(|node| node.passable && node.weight > 0);
+    }
 
-            result.processed.push(current.clone());
+    /// Generates a maze, recording each change it makes into `events` when that is `Some`.
+    fn build_maze(
+        &self,
+        maze_type: MazeType,
+        seed: Option<u32>,
+        options: &MazeOptions,
+        events: &mut Option<Vec<MazeEvent>>,
+    ) -> Result<Vec<GridNode>, UniverseError> {
+        options.validate()?;
+
+        let mut rng = match seed {
+            Some(seed) => ChaCha8Rng::seed_from_u64(seed as u64),
+            None => ChaCha8Rng::from_entropy(),
+        };
 
-            if current == end_node {
-                return Universe::construct_path(result, came_from, end_node);
+        let mut maze: Vec<GridNode> = Vec::new();
+        let mut weighted: Vec<GridNode> = Vec::new();
+        match maze_type {
+            MazeType::Random25 => self.random_nodes(&mut maze, &mut rng, 25),
+            MazeType::Random50 => self.random_nodes(&mut maze, &mut rng, 50),
+            MazeType::Random75 => self.random_nodes(&mut maze, &mut rng, 75),
+            MazeType::RecursiveDivision => self.recursive_division_maze(
+                &mut maze,
+                &mut rng,
+                2,
+                self.height as i32 - 3,
+                2,
+                self.width as i32 - 3,
+                Orientation::Horizontal,
+                true,
+            ),
+            MazeType::Prim => self.prim_maze(&mut maze, &mut rng, events),
+            MazeType::Kruskal => self.kruskal_maze(&mut maze, &mut rng, events),
+            MazeType::Wilson => self.wilson_maze(&mut maze, &mut rng, events),
+            MazeType::AldousBroder => self.aldous_broder_maze(&mut maze, &mut rng, events),
+            MazeType::RecursiveBacktracker => {
+                self.recursive_backtracker_maze(&mut maze, &mut rng, events)
             }
+            MazeType::HuntAndKill => self.hunt_and_kill_maze(&mut maze, &mut rng, events),
+            MazeType::Caves => self.caves_maze(&mut maze, &mut rng, options, events),
+            MazeType::Dungeon => {
+                self.dungeon_maze(&mut maze, &mut weighted, &mut rng, options, events)
+            }
+        };
 
-            for neighbor in self.get_neighbors(current.x, current.y) {
-                if !visited.contains(neighbor) {
-                    visited.insert(neighbor);
-                    came_from.insert(neighbor, current);
-                    frontier.push_back(neighbor);
+        // These generators only ever add walls, so the walls they return are their construction order.
+        if let (
+            Some(events),
+            MazeType::Random25
+            | MazeType::Random50
+            | MazeType::Random75
+            | MazeType::RecursiveDivision,
+        ) = (events.as_mut(), maze_type)
+        {
+            let mut added = vec![false; self.nodes.len()];
+
+            for node in &maze {
+                let index = self.get_index(node.x, node.y);
+
+                if !added[index] {
+                    added[index] = true;
+                    events.push(MazeEvent::WallAdded {
+                        node: GridNode {
+                            passable: false,
+                            ..*node
+                        },
+                    });
                 }
             }
         }
 
-        return result;
-    }
+        if options.braid > 0.0 {
+            maze = self.braid_maze(maze, &mut rng, options.braid, events);
+        }
 
-    fn bfs_bidirectional(&self, start_x: i32, start_y: i32, end_x: i32, end_y: i32) -> PathResult {
-        let mut result = PathResult {
-            path: Vec::new(),
-            processed: Vec::new(),
-        };
+        if let (Some(start), Some(end)) = (options.start, options.end) {
+            maze = self.connect_maze_endpoints(maze, start, end, options.clear, events)?;
+        }
 
-        let mut frontier_start: VecDeque<&GridNode> = VecDeque::new();
-        let mut frontier_end: VecDeque<&GridNode> = VecDeque::new();
+        for node in &mut maze {
+            node.passable = false;
+            node.weight = 0;
+        }
 
-        let start_node = self.get_node_ref(start_x, start_y);
-        let end_node = self.get_node_ref(end_x, end_y);
+        maze.extend(weighted);
 
-        let mut came_from_start: HashMap<&GridNode, &GridNode> = HashMap::new();
-        let mut came_from_end: HashMap<&GridNode, &GridNode> = HashMap::new();
-        let mut visited_start: HashSet<&GridNode> = HashSet::new();
-        let mut visited_end: HashSet<&GridNode> = HashSet::new();
+        return Ok(maze);
+    }
 
-        frontier_start.push_back(start_node);
-        frontier_end.push_back(end_node);
-        visited_start.insert(start_node);
-        visited_end.insert(end_node);
+    /// Opens `start` and `end` and, if the maze separates them, knocks out the fewest maze walls that joins them.
+    /// Walls already on the grid are kept unless the maze is going to be applied to a cleared grid.
+    fn connect_maze_endpoints(
+        &self,
+        maze: Vec<GridNode>,
+        start: Point,
+        end: Point,
+        clear: bool,
+        events: &mut Option<Vec<MazeEvent>>,
+    ) -> Result<Vec<GridNode>, UniverseError> {
+        for (name, point) in [("start", start), ("end", end)] {
+            if !self.has_node(point.x, point.y) {
+                return Err(UniverseError::InvalidOptions(format!(
+                    "maze {} {},{} is outside the {}x{} grid",
+                    name, point.x, point.y, self.width, self.height
+                )));
+            }
 
-        while frontier_start.len() > 0 || frontier_end.len() > 0 {
-            if frontier_start.len() > 0 {
-                let current = frontier_start.pop_front().unwrap();
-                result.processed.push(current.clone());
+            if !clear && !self.get_node_ref(point.x, point.y).passable {
+                return Err(UniverseError::InvalidOptions(format!(
+                    "maze {} {},{} is already a wall",
+                    name, point.x, point.y
+                )));
+            }
+        }
 
-                if current == end_node {
-                    return Universe::construct_path(result, came_from_start, end_node);
-                }
+        let mut walls = vec![false; self.nodes.len()];
 
-                if came_from_end.contains_key(current) {
-                    let mut here_end = Universe::construct_path(result, came_from_end, current);
-                    here_end.path.pop();
-                    let start_here = Universe::construct_path(here_end, came_from_start, current);
+        for node in &maze {
+            walls[self.get_index(node.x, node.y)] = true;
+        }
 
-                    return start_here;
-                }
+        let start_index = self.get_index(start.x, start.y);
+        let end_index = self.get_index(end.x, end.y);
+        let mut carved: Vec<usize> = [start_index, end_index]
+            .into_iter()
+            .filter(|index| walls[*index])
+            .collect();
 
-                for neighbor in self.get_neighbors(current.x, current.y) {
-                    if !visited_start.contains(neighbor) {
-                        visited_start.insert(neighbor);
-                        came_from_start.insert(neighbor, current);
-                        frontier_start.push_back(neighbor);
-                    }
-                }
-            }
+        carved.dedup();
 
-            if frontier_end.len() > 0 {
-                let current = frontier_end.pop_front().unwrap();
-                result.processed.push(current.clone());
+        for index in &carved {
+            walls[*index] = false;
+        }
 
-                if current == start_node {
-                    return Universe::construct_path(result, came_from_end, start_node);
+        // 0-1 breadth-first search: stepping through a maze wall costs one and open ground costs nothing, so the
+        // cheapest route to the end knocks out the fewest walls. Four-way steps keep the result valid for any
+        // connectivity.
+        let mut cost = vec![usize::MAX; self.nodes.len()];
+        let mut came_from: Vec<Option<usize>> = vec![None; self.nodes.len()];
+        let mut queue = VecDeque::from([start_index]);
+
+        cost[start_index] = 0;
+
+        while let Some(index) = queue.pop_front() {
+            let node = self.nodes[index];
+
+            for (x, y) in [
+                (node.x, node.y - 1),
+                (node.x, node.y + 1),
+                (node.x - 1, node.y),
+                (node.x + 1, node.y),
+            ] {
+                if !self.has_node(x, y) {
+                    continue;
                 }
 
-                if came_from_start.contains_key(current) {
-                    let mut here_end = Universe::construct_path(result, came_from_end, current);
-                    here_end.path.pop();
-                    let start_here = Universe::construct_path(here_end, came_from_start, current);
+                let neighbor = self.get_index(x, y);
 
-                    return start_here;
+                if !clear && !self.nodes[neighbor].passable {
+                    continue;
                 }
 
-                for neighbor in self.get_neighbors(current.x, current.y) {
-                    if !visited_end.contains(neighbor) {
-                        visited_end.insert(neighbor);
-                        came_from_end.insert(neighbor, current);
-                        frontier_end.push_back(neighbor);
+                let step = walls[neighbor] as usize;
+
+                if cost[index] + step < cost[neighbor] {
+                    cost[neighbor] = cost[index] + step;
+                    came_from[neighbor] = Some(index);
+
+                    if step == 0 {
+                        queue.push_front(neighbor);
+                    } else {
+                        queue.push_back(neighbor);
                     }
                 }
             }
         }
 
-        return result;
-    }
+        if cost[end_index] == usize::MAX {
+            return Err(UniverseError::InvalidOptions(format!(
+                "maze start {},{} and end {},{} are separated by walls already on the grid",
+                start.x, start.y, end.x, end.y
+            )));
+        }
 
-    fn greedy_bfs(&self, start_x: i32, start_y: i32, end_x: i32, end_y: i32) -> PathResult {
-        let mut result = PathResult {
-            path: Vec::new(),
-            processed: Vec::new(),
-        };
+        let mut current = end_index;
+
+        while let Some(previous) = came_from[current] {
+            if walls[current] {
+                walls[current] = false;
+                carved.push(current);
+            }
+
+            current = previous;
+        }
 
-        let mut frontier: PriorityQueue<&GridNode> = PriorityQueue::new();
+        for index in carved {
+            MazeEvent::record(events, || MazeEvent::WallRemoved {
+                node: GridNode {
+                    passable: true,
+                    ..self.nodes[index]
+                },
+            });
+        }
 
-        let start_node = self.get_node_ref(start_x, start_y);
-        let end_node = self.get_node_ref(end_x, end_y);
+        return Ok(maze
+            .into_iter()
+            .filter(|node| walls[self.get_index(node.x, node.y)])
+            .collect());
+    }
 
-        let mut came_from: HashMap<&GridNode, &GridNode> = HashMap::new();
-        let mut visited: HashSet<&GridNode> = HashSet::new();
+    /// Knocks a wall out of the given fraction of dead ends, preferring walls that also open up a neighboring dead end.
+    fn braid_maze(
+        &self,
+        maze: Vec<GridNode>,
+        rng: &mut ChaCha8Rng,
+        braid: f64,
+        events: &mut Option<Vec<MazeEvent>>,
+    ) -> Vec<GridNode> {
+        let mut walls = vec![false; self.nodes.len()];
+
+        for node in &maze {
+            walls[self.get_index(node.x, node.y)] = true;
+        }
 
-        frontier.enqueue(start_node, 0);
-        visited.insert(start_node);
+        let directions = [(0, -1), (0, 1), (-1, 0), (1, 0)];
+        let is_open =
+            |walls: &[bool], x: i32, y: i32| self.has_node(x, y) && !walls[self.get_index(x, y)];
+        let is_dead_end = |walls: &[bool], x: i32, y: i32| {
+            is_open(walls, x, y)
+                && directions
+                    .iter()
+                    .filter(|(dx, dy)| is_open(walls, x + dx, y + dy))
+                    .count()
+                    == 1
+        };
 
-        while frontier.count() > 0 {
-            let current = frontier.dequeue().unwrap();
+        let mut dead_ends: Vec<(i32, i32)> = self
+            .nodes
+            .iter()
+            .filter(|node| is_dead_end(&walls, node.x, node.y))
+            .map(|node| (node.x, node.y))
+            .collect();
 
-            result.processed.push(current.clone());
+        shuffle(rng, &mut dead_ends);
+        let braided = (dead_ends.len() as f64 * braid).round() as usize;
 
-            if current == end_node {
-                return Universe::construct_path(result, came_from, end_node);
+        for &(x, y) in dead_ends.iter().take(braided) {
+            // An earlier knockout may already have opened this dead end up.
+            if !is_dead_end(&walls, x, y) {
+                continue;
             }
 
-            for neighbor in self.get_neighbors(current.x, current.y) {
-                if !visited.contains(neighbor) {
-                    visited.insert(neighbor);
-                    came_from.insert(neighbor, current);
-                    frontier.enqueue(
-                        neighbor,
-                        -1 * (Universe::heuristic(neighbor, end_node) + neighbor.weight),
-                    );
-                }
+            let candidates: Vec<(i32, i32)> = directions
+                .iter()
+                .filter(|(dx, dy)| {
+                    !is_open(&walls, x + dx, y + dy)
+                        && self.has_node(x + dx, y + dy)
+                        && is_open(&walls, x + dx * 2, y + dy * 2)
+                })
+                .map(|(dx, dy)| (x + dx, y + dy))
+                .collect();
+
+            let preferred: Vec<(i32, i32)> = candidates
+                .iter()
+                .copied()
+                .filter(|(wx, wy)| is_dead_end(&walls, wx * 2 - x, wy * 2 - y))
+                .collect();
+
+            let choices = if preferred.is_empty() {
+                candidates
+            } else {
+                preferred
+            };
+
+            if !choices.is_empty() {
+                let (wx, wy) = choices[random_index(rng, choices.len())];
+                walls[self.get_index(wx, wy)] = false;
+
+                MazeEvent::record(events, || MazeEvent::WallRemoved {
+                    node: GridNode {
+                        passable: true,
+                        ..*self.get_node_ref(wx, wy)
+                    },
+                });
             }
         }
 
-        return result;
+        return maze
+            .into_iter()
+            .filter(|node| walls[self.get_index(node.x, node.y)])
+            .collect();
     }
 
-    fn dfs(&self, start_x: i32, start_y: i32, end_x: i32, end_y: i32) -> PathResult {
-        let mut result = PathResult {
-            path: Vec::new(),
-            processed: Vec::new(),
-        };
+    #[allow(clippy::needless_lifetimes, clippy::clone_on_copy)]
+    fn random_nodes<'a>(
+        &self,
+        result: &'a mut Vec<GridNode>,
+        rng: &mut ChaCha8Rng,
+        percentage: i32,
+    ) {
+        for node in &self.nodes {
+            let rand = rng.gen_range(0..100);
 
-        let mut frontier: Vec<&GridNode> = Vec::new();
+            if rand < percentage {
+                result.push(node.clone());
+            }
+        }
+    }
 
-        let start_node = self.get_node_ref(start_x, start_y);
-        let end_node = self.get_node_ref(end_x, end_y);
+    fn caves_maze(
+        &self,
+        result: &mut Vec<GridNode>,
+        rng: &mut ChaCha8Rng,
+        options: &MazeOptions,
+        events: &mut Option<Vec<MazeEvent>>,
+    ) {
+        let mut walls: Vec<bool> = self
+            .nodes
+            .iter()
+            .map(|_| rng.gen_range(0..100) < options.cave_fill)
+            .collect();
+
+        self.record_wall_changes(events, &vec![false; walls.len()], &walls);
+
+        // A wall survives with four or more wall neighbors and an open node fills in with five or more, counting the
+        // outside of the grid as wall.
+        for _ in 0..options.cave_passes {
+            let smoothed: Vec<bool> = self
+                .nodes
+                .iter()
+                .map(|node| {
+                    let mut wall_neighbors = 0;
+
+                    for direction_y in -1..=1 {
+                        for direction_x in -1..=1 {
+                            let x = node.x + direction_x;
+                            let y = node.y + direction_y;
+
+                            if (direction_x != 0 || direction_y != 0)
+                                && (!self.has_node(x, y) || walls[self.get_index(x, y)])
+                            {
+                                wall_neighbors += 1;
+                            }
+                        }
+                    }
 
-        let mut came_from: HashMap<&GridNode, &GridNode> = HashMap::new();
-        let mut visited: HashSet<&GridNode> = HashSet::new();
+                    return if walls[self.get_index(node.x, node.y)] {
+                        wall_neighbors >= 4
+                    } else {
+                        wall_neighbors >= 5
+                    };
+                })
+                .collect();
 
-        frontier.push(start_node);
+            self.record_wall_changes(events, &walls, &smoothed);
+            walls = smoothed;
+        }
 
-        while frontier.len() > 0 {
-            let current = frontier.pop().unwrap();
+        // Fill every open pocket except the largest so all remaining open nodes can reach each other.
+        let mut regions = UnionFind::new(self.nodes.len());
 
-            result.processed.push(current.clone());
-            visited.insert(current);
+        for node in &self.nodes {
+            let index = self.get_index(node.x, node.y);
 
-            if current == end_node {
-                return Universe::construct_path(result, came_from, end_node);
+            for (x, y) in [(node.x + 1, node.y), (node.x, node.y + 1)] {
+                if !walls[index] && self.has_node(x, y) && !walls[self.get_index(x, y)] {
+                    regions.union(index, self.get_index(x, y));
+                }
             }
+        }
 
-            for neighbor in self.get_neighbors(current.x, current.y) {
-                if !visited.contains(neighbor) {
-                    came_from.insert(neighbor, current);
-                    frontier.push(neighbor);
-                }
+        let largest = (0..self.nodes.len())
+            .filter(|index| !walls[*index])
+            .max_by_key(|index| regions.set_size(*index))
+            .map(|index| regions.find(index));
+
+        let filled: Vec<bool> = (0..self.nodes.len())
+            .map(|index| walls[index] || Some(regions.find(index)) != largest)
+            .collect();
+
+        self.record_wall_changes(events, &walls, &filled);
+
+        for (index, node) in self.nodes.iter().enumerate() {
+            if filled[index] {
+                result.push(*node);
             }
         }
+    }
 
-        return result;
+    fn record_wall_changes(
+        &self,
+        events: &mut Option<Vec<MazeEvent>>,
+        before: &[bool],
+        after: &[bool],
+    ) {
+        for (index, node) in self.nodes.iter().enumerate() {
+            let node = GridNode {
+                passable: !after[index],
+                ..*node
+            };
+
+            match (before[index], after[index]) {
+                (false, true) => MazeEvent::record(events, || MazeEvent::WallAdded { node }),
+                (true, false) => MazeEvent::record(events, || MazeEvent::WallRemoved { node }),
+                _ => {}
+            }
+        }
     }
 
-    fn dijkstra(&self, start_x: i32, start_y: i32, end_x: i32, end_y: i32) -> PathResult {
-        let mut result = PathResult {
-            path: Vec::new(),
-            processed: Vec::new(),
-        };
+    /// Splits the grid into leaves by binary space partitioning, places a room in each leaf and joins sibling subtrees
+    /// with L-shaped corridors. Doorways, where a corridor meets a room, go into `weighted` if given a weight.
+    fn dungeon_maze(
+        &self,
+        result: &mut Vec<GridNode>,
+        weighted: &mut Vec<GridNode>,
+        rng: &mut ChaCha8Rng,
+        options: &MazeOptions,
+        events: &mut Option<Vec<MazeEvent>>,
+    ) {
+        let mut open = vec![false; self.nodes.len()];
+        let mut rooms: Vec<(i32, i32, i32, i32)> = Vec::new();
+
+        self.split_dungeon(
+            &mut open,
+            &mut rooms,
+            rng,
+            events,
+            (1, 1, self.width as i32 - 2, self.height as i32 - 2),
+        );
+
+        let mut in_room = vec![false; self.nodes.len()];
+
+        for &(x, y, width, height) in &rooms {
+            for room_y in y..y + height {
+                for room_x in x..x + width {
+                    in_room[self.get_index(room_x, room_y)] = true;
+                }
+            }
+        }
+
+        for (index, node) in self.nodes.iter().enumerate() {
+            if !open[index] {
+                result.push(*node);
+                continue;
+            }
 
-        let mut frontier: PriorityQueue<&GridNode> = PriorityQueue::new();
+            let is_doorway = !in_room[index]
+                && [(0, -1), (0, 1), (-1, 0), (1, 0)].iter().any(|(dx, dy)| {
+                    self.has_node(node.x + dx, node.y + dy)
+                        && in_room[self.get_index(node.x + dx, node.y + dy)]
+                });
 
-        let start_node = self.get_node_ref(start_x, start_y);
-        let end_node = self.get_node_ref(end_x, end_y);
+            if is_doorway && options.doorway_weight > 0 {
+                weighted.push(GridNode {
+                    passable: true,
+                    weight: options.doorway_weight,
+                    ..*node
+                });
+            }
+        }
+    }
 
-        let mut times: HashMap<&GridNode, i32> = HashMap::new();
-        let mut came_from: HashMap<&GridNode, &GridNode> = HashMap::new();
+    /// Carves the rooms and corridors for one leaf of the partition, returning the indices of the rooms inside it.
+    fn split_dungeon(
+        &self,
+        open: &mut [bool],
+        rooms: &mut Vec<(i32, i32, i32, i32)>,
+        rng: &mut ChaCha8Rng,
+        events: &mut Option<Vec<MazeEvent>>,
+        (x, y, width, height): (i32, i32, i32, i32),
+    ) -> Vec<usize> {
+        // A leaf needs room for the smallest room plus a wall on either side.
+        const MIN_LEAF_SIZE: i32 = 7;
+        const MIN_ROOM_SIZE: i32 = 3;
+
+        let can_split_columns = width >= MIN_LEAF_SIZE * 2;
+        let can_split_rows = height >= MIN_LEAF_SIZE * 2;
+        let split_columns = match (can_split_columns, can_split_rows) {
+            (true, true) if width * 4 > height * 5 => Some(true),
+            (true, true) if height * 4 > width * 5 => Some(false),
+            (true, true) => Some(rng.gen_bool(0.5)),
+            (true, false) => Some(true),
+            (false, true) => Some(false),
+            (false, false) => None,
+        };
 
-        times.insert(start_node, 0);
-        frontier.enqueue(start_node, 0);
+        let (first, second) = match split_columns {
+            Some(true) => {
+                let split = rng.gen_range(MIN_LEAF_SIZE..=width - MIN_LEAF_SIZE);
+                ((x, y, split, height), (x + split, y, width - split, height))
+            }
+            Some(false) => {
+                let split = rng.gen_range(MIN_LEAF_SIZE..=height - MIN_LEAF_SIZE);
+                ((x, y, width, split), (x, y + split, width, height - split))
+            }
+            None => {
+                if width < 3 || height < 3 {
+                    return Vec::new();
+                }
 
-        while frontier.count() > 0 {
-            let current = frontier.dequeue().unwrap();
+                let room_width = rng.gen_range(MIN_ROOM_SIZE.min(width - 2)..=width - 2);
+                let room_height = rng.gen_range(MIN_ROOM_SIZE.min(height - 2)..=height - 2);
+                let room_x = rng.gen_range(x + 1..=x + width - 1 - room_width);
+                let room_y = rng.gen_range(y + 1..=y + height - 1 - room_height);
 
-            result.processed.push(current.clone());
+                for cell_y in room_y..room_y + room_height {
+                    for cell_x in room_x..room_x + room_width {
+                        self.carve_dungeon_node(open, events, cell_x, cell_y);
+                    }
+                }
 
-            if current == end_node {
-                return Universe::construct_path(result, came_from, end_node);
+                rooms.push((room_x, room_y, room_width, room_height));
+                return vec![rooms.len() - 1];
             }
+        };
 
-            for neighbor in self.get_neighbors(current.x, current.y) {
-                let prev_time = times.get(current).unwrap();
-                let new_time = *prev_time + neighbor.weight + 1;
+        let first_rooms = self.split_dungeon(open, rooms, rng, events, first);
+        let second_rooms = self.split_dungeon(open, rooms, rng, events, second);
+
+        if !first_rooms.is_empty() && !second_rooms.is_empty() {
+            let from = rooms[first_rooms[random_index(rng, first_rooms.len())]];
+            let to = rooms[second_rooms[random_index(rng, second_rooms.len())]];
+            let (from_x, from_y) = (
+                rng.gen_range(from.0..from.0 + from.2),
+                rng.gen_range(from.1..from.1 + from.3),
+            );
+            let (to_x, to_y) = (
+                rng.gen_range(to.0..to.0 + to.2),
+                rng.gen_range(to.1..to.1 + to.3),
+            );
+            let corner = if rng.gen_bool(0.5) {
+                (to_x, from_y)
+            } else {
+                (from_x, to_y)
+            };
 
-                if new_time < *times.get(neighbor).unwrap_or(&i32::MAX) {
-                    times.insert(neighbor, new_time);
-                    came_from.insert(neighbor, current);
-                    frontier.enqueue(neighbor, -1 * new_time);
+            for (start, end) in [((from_x, from_y), corner), (corner, (to_x, to_y))] {
+                for cell_x in start.0.min(end.0)..=start.0.max(end.0) {
+                    for cell_y in start.1.min(end.1)..=start.1.max(end.1) {
+                        self.carve_dungeon_node(open, events, cell_x, cell_y);
+                    }
                 }
             }
         }
 
-        return result;
+        return [first_rooms, second_rooms].concat();
     }
 
-    fn astar(&self, start_x: i32, start_y: i32, end_x: i32, end_y: i32) -> PathResult {
-        let mut result: PathResult = PathResult {
-            path: Vec::new(),
-            processed: Vec::new(),
-        };
+    fn carve_dungeon_node(
+        &self,
+        open: &mut [bool],
+        events: &mut Option<Vec<MazeEvent>>,
+        x: i32,
+        y: i32,
+    ) {
+        let index = self.get_index(x, y);
 
-        let start_node = self.get_node_ref(start_x, start_y);
-        let end_node = self.get_node_ref(end_x, end_y);
+        if !open[index] {
+            open[index] = true;
+            MazeEvent::record(events, || MazeEvent::CellCarved {
+                node: GridNode {
+                    passable: true,
+                    ..self.nodes[index]
+                },
+            });
+        }
+    }
 
-        let mut came_from: HashMap<&GridNode, &GridNode> = HashMap::new();
-        let mut g_score: HashMap<&GridNode, i32> = HashMap::new();
+    fn prim_maze(
+        &self,
+        result: &mut Vec<GridNode>,
+        rng: &mut ChaCha8Rng,
+        events: &mut Option<Vec<MazeEvent>>,
+    ) {
+        let mut lattice = MazeLattice::new(self, events);
 
-        g_score.insert(start_node, 0);
+        if lattice.cell_count() == 0 {
+            result.extend(lattice.into_walls());
+            return;
+        }
 
-        let mut frontier: PriorityQueue<&GridNode> = PriorityQueue::new();
+        let mut in_frontier = vec![false; lattice.cell_count()];
+        let mut frontier: Vec<usize> = Vec::new();
+        let root = random_index(rng, lattice.cell_count());
 
-        frontier.enqueue(start_node, 0);
+        lattice.carve_cell(root);
 
-        while frontier.count() > 0 {
-            let current = frontier.dequeue().unwrap();
-            result.processed.push(current.clone());
+        for neighbor in lattice.neighbors(root) {
+            in_frontier[neighbor] = true;
+            frontier.push(neighbor);
+        }
 
-            if current == end_node {
-                return Universe::construct_path(result, came_from, end_node);
-            }
+        while !frontier.is_empty() {
+            let cell = frontier.swap_remove(random_index(rng, frontier.len()));
+            let carved: Vec<usize> = lattice
+                .neighbors(cell)
+                .into_iter()
+                .filter(|neighbor| lattice.is_carved(*neighbor))
+                .collect();
 
-            for next in self.get_neighbors(current.x, current.y) {
-                let tentative_g_score = 1 + *g_score.get(current).unwrap() + next.weight as i32;
+            lattice.carve_passage(carved[random_index(rng, carved.len())], cell);
 
-                if tentative_g_score < *g_score.get(next).unwrap_or(&i32::MAX) {
-                    g_score.insert(next, tentative_g_score);
-                    let f_score = -1 * (tentative_g_score + Universe::heuristic(next, end_node));
-                    frontier.enqueue(next, f_score);
-                    came_from.insert(next, current);
+            for neighbor in lattice.neighbors(cell) {
+                if !in_frontier[neighbor] && !lattice.is_carved(neighbor) {
+                    in_frontier[neighbor] = true;
+                    frontier.push(neighbor);
                 }
             }
         }
 
-        return result;
+        result.extend(lattice.into_walls());
     }
 
-    fn astar_bidirectional(
+    fn kruskal_maze(
         &self,
-        start_x: i32,
-        start_y: i32,
-        end_x: i32,
-        end_y: i32,
-    ) -> PathResult {
-        let mut result: PathResult = PathResult {
-            path: Vec::new(),
-            processed: Vec::new(),
-        };
+        result: &mut Vec<GridNode>,
+        rng: &mut ChaCha8Rng,
+        events: &mut Option<Vec<MazeEvent>>,
+    ) {
+        let mut lattice = MazeLattice::new(self, events);
+        let mut sets = UnionFind::new(lattice.cell_count());
+        let mut candidates: Vec<(usize, usize)> = Vec::new();
 
-        let start_node = self.get_node_ref(start_x, start_y);
-        let end_node = self.get_node_ref(end_x, end_y);
+        for cell in 0..lattice.cell_count() {
+            lattice.carve_cell(cell);
+
+            for neighbor in lattice.neighbors(cell) {
+                if neighbor > cell {
+                    candidates.push((cell, neighbor));
+                }
+            }
+        }
 
-        let mut came_from_start: HashMap<&GridNode, &GridNode> = HashMap::new();
-        let mut came_from_end: HashMap<&GridNode, &GridNode> = HashMap::new();
-        let mut g_score_start: HashMap<&GridNode, i32> = HashMap::new();
-        let mut g_score_end: HashMap<&GridNode, i32> = HashMap::new();
+        shuffle(rng, &mut candidates);
 
-        g_score_start.insert(start_node, 0);
-        g_score_end.insert(end_node, 0);
+        for (cell, neighbor) in candidates {
+            if sets.union(cell, neighbor) {
+                lattice.carve_passage(cell, neighbor);
+            }
+        }
 
-        let mut frontier_start: PriorityQueue<&GridNode> = PriorityQueue::new();
-        let mut frontier_end: PriorityQueue<&GridNode> = PriorityQueue::new();
+        result.extend(lattice.into_walls());
+    }
 
-        frontier_start.enqueue(start_node, 0);
-        frontier_end.enqueue(end_node, 0);
+    fn wilson_maze(
+        &self,
+        result: &mut Vec<GridNode>,
+        rng: &mut ChaCha8Rng,
+        events: &mut Option<Vec<MazeEvent>>,
+    ) {
+        let mut lattice = MazeLattice::new(self, events);
 
-        while frontier_start.count() > 0 || frontier_end.count() > 0 {
-            if frontier_start.count() > 0 {
-                let current = frontier_start.dequeue().unwrap();
-                result.processed.push(current.clone());
+        if lattice.cell_count() == 0 {
+            result.extend(lattice.into_walls());
+            return;
+        }
 
-                if current == end_node {
-                    return Universe::construct_path(result, came_from_start, end_node);
-                }
+        let mut next: Vec<usize> = vec![0; lattice.cell_count()];
+        lattice.carve_cell(random_index(rng, lattice.cell_count()));
 
-                if came_from_end.contains_key(current) {
-                    let mut here_end = Universe::construct_path(result, came_from_end, current);
-                    here_end.path.pop();
-                    let start_here = Universe::construct_path(here_end, came_from_start, current);
+        for start in 0..lattice.cell_count() {
+            // Walk until the maze is hit, remembering only the last exit from each cell so loops erase themselves.
+            let mut cell = start;
 
-                    return start_here;
-                }
+            while !lattice.is_carved(cell) {
+                let neighbors = lattice.neighbors(cell);
+                next[cell] = neighbors[random_index(rng, neighbors.len())];
+                cell = next[cell];
+            }
 
-                for next in self.get_neighbors(current.x, current.y) {
-                    let tentative_g_score =
-                        1 + *g_score_start.get(current).unwrap() + next.weight as i32;
+            cell = start;
 
-                    if tentative_g_score < *g_score_start.get(next).unwrap_or(&i32::MAX) {
-                        g_score_start.insert(next, tentative_g_score);
-                        let f_score =
-                            -1 * (tentative_g_score + Universe::heuristic(next, end_node));
-                        frontier_start.enqueue(next, f_score);
-                        came_from_start.insert(next, current);
-                    }
-                }
+            while !lattice.is_carved(cell) {
+                lattice.carve_passage(next[cell], cell);
+                cell = next[cell];
             }
+        }
+
+        result.extend(lattice.into_walls());
+    }
 
-            if frontier_end.count() > 0 {
-                let current = frontier_end.dequeue().unwrap();
-                result.processed.push(current.clone());
+    fn aldous_broder_maze(
+        &self,
+        result: &mut Vec<GridNode>,
+        rng: &mut ChaCha8Rng,
+        events: &mut Option<Vec<MazeEvent>>,
+    ) {
+        let mut lattice = MazeLattice::new(self, events);
 
-                if current == start_node {
-                    return Universe::construct_path(result, came_from_end, start_node);
-                }
+        if lattice.cell_count() == 0 {
+            result.extend(lattice.into_walls());
+            return;
+        }
 
-                if came_from_start.contains_key(current) {
-                    let mut here_end = Universe::construct_path(result, came_from_end, current);
-                    here_end.path.pop();
-                    let start_here = Universe::construct_path(here_end, came_from_start, current);
+        let mut cell = random_index(rng, lattice.cell_count());
+        let mut remaining = lattice.cell_count() - 1;
 
-                    return start_here;
-                }
+        lattice.carve_cell(cell);
 
-                for next in self.get_neighbors(current.x, current.y) {
-                    let tentative_g_score =
-                        1 + *g_score_end.get(current).unwrap() + next.weight as i32;
+        while remaining > 0 {
+            let neighbors = lattice.neighbors(cell);
+            let neighbor = neighbors[random_index(rng, neighbors.len())];
 
-                    if tentative_g_score < *g_score_end.get(next).unwrap_or(&i32::MAX) {
-                        g_score_end.insert(next, tentative_g_score);
-                        let f_score =
-                            -1 * (tentative_g_score + Universe::heuristic(next, start_node));
-                        frontier_end.enqueue(next, f_score);
-                        came_from_end.insert(next, current);
-                    }
-                }
+            if !lattice.is_carved(neighbor) {
+                lattice.carve_passage(cell, neighbor);
+                remaining -= 1;
             }
+
+            cell = neighbor;
         }
 
-        return result;
+        result.extend(lattice.into_walls());
     }
 
-    fn get_neighbors(&self, x: i32, y: i32) -> Vec<&GridNode> {
-        let mut vec: Vec<&GridNode> = Vec::with_capacity(4);
+    /// Uses an explicit stack rather than recursion so large grids cannot overflow the wasm stack.
+    fn recursive_backtracker_maze(
+        &self,
+        result: &mut Vec<GridNode>,
+        rng: &mut ChaCha8Rng,
+        events: &mut Option<Vec<MazeEvent>>,
+    ) {
+        let mut lattice = MazeLattice::new(self, events);
 
-        if y > 0 && self.get_node_ref(x, y - 1).passable {
-            vec.push(self.get_node_ref(x, y - 1));
+        if lattice.cell_count() == 0 {
+            result.extend(lattice.into_walls());
+            return;
         }
 
-        if y < self.height as i32 - 1 && self.get_node_ref(x, y + 1).passable {
-            vec.push(self.get_node_ref(x, y + 1));
-        }
+        let root = random_index(rng, lattice.cell_count());
+        let mut stack = vec![root];
 
-        if x > 0 && self.get_node_ref(x - 1, y).passable {
-            vec.push(self.get_node_ref(x - 1, y));
-        }
+        lattice.carve_cell(root);
+
+        while let Some(&cell) = stack.last() {
+            let uncarved: Vec<usize> = lattice
+                .neighbors(cell)
+                .into_iter()
+                .filter(|neighbor| !lattice.is_carved(*neighbor))
+                .collect();
 
-        if x < self.width as i32 - 1 && self.get_node_ref(x + 1, y).passable {
-            vec.push(self.get_node_ref(x + 1, y));
+            if uncarved.is_empty() {
+                stack.pop();
+                continue;
+            }
+
+            let neighbor = uncarved[random_index(rng, uncarved.len())];
+            lattice.carve_passage(cell, neighbor);
+            stack.push(neighbor);
         }
 
-        return vec;
+        result.extend(lattice.into_walls());
     }
 
-    fn construct_path(
-        mut result: PathResult,
-        came_from: HashMap<&GridNode, &GridNode>,
-        end_node: &GridNode,
-    ) -> PathResult {
-        let mut current = end_node;
-        result.path.push(current.clone());
+    fn hunt_and_kill_maze(
+        &self,
+        result: &mut Vec<GridNode>,
+        rng: &mut ChaCha8Rng,
+        events: &mut Option<Vec<MazeEvent>>,
+    ) {
+        let mut lattice = MazeLattice::new(self, events);
 
-        while let Some(next) = came_from.get(current) {
-            current = next;
-            result.path.push(current.clone());
+        if lattice.cell_count() == 0 {
+            result.extend(lattice.into_walls());
+            return;
         }
 
-        result.path.reverse();
+        let mut cell = random_index(rng, lattice.cell_count());
+        // Every cell before this one is already carved, so the hunt never rescans them.
+        let mut hunt_start = 0;
 
-        return result;
-    }
+        lattice.carve_cell(cell);
 
-    fn heuristic(a: &GridNode, b: &GridNode) -> i32 {
-        let distance_x = (a.x - b.x).abs();
-        let distance_y = (a.y - b.y).abs();
+        loop {
+            let uncarved: Vec<usize> = lattice
+                .neighbors(cell)
+                .into_iter()
+                .filter(|neighbor| !lattice.is_carved(*neighbor))
+                .collect();
 
-        return distance_x + distance_y;
-    }
-}
+            if !uncarved.is_empty() {
+                let neighbor = uncarved[random_index(rng, uncarved.len())];
+                lattice.carve_passage(cell, neighbor);
+                cell = neighbor;
+                continue;
+            }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+            while hunt_start < lattice.cell_count() && lattice.is_carved(hunt_start) {
+                hunt_start += 1;
+            }
 
-    #[test]
-    fn astar_works() {
-        let w: i32 = -1;
-        let h = 1000;
+            let hunted = (hunt_start..lattice.cell_count()).find(|candidate| {
+                !lattice.is_carved(*candidate)
+                    && lattice
+                        .neighbors(*candidate)
+                        .iter()
+                        .any(|neighbor| lattice.is_carved(*neighbor))
+            });
+
+            let hunted = match hunted {
+                Some(hunted) => hunted,
+                None => break,
+            };
+
+            let carved: Vec<usize> = lattice
+                .neighbors(hunted)
+                .into_iter()
+                .filter(|neighbor| lattice.is_carved(*neighbor))
+                .collect();
+
+            lattice.carve_passage(carved[random_index(rng, carved.len())], hunted);
+            cell = hunted;
+        }
 
-        let grid_w = [
-            [0, 0, w, 0, 0],
-            [0, 0, w, 0, 0],
-            [0, 0, w, 0, 0],
-            [0, 0, h, 0, 0],
-            [0, 0, 0, 0, 0],
-        ];
+        result.extend(lattice.into_walls());
+    }
 
-        let mut universe = Universe::new(grid_w[0].len() as u32, grid_w.len() as u32);
+    #[allow(clippy::too_many_arguments, clippy::needless_lifetimes)]
+    fn recursive_division_maze<'a>(
+        &self,
+        result: &'a mut Vec<GridNode>,
+        rng: &mut ChaCha8Rng,
+        row_start: i32,
+        row_end: i32,
+        col_start: i32,
+        col_end: i32,
+        orientation: Orientation,
+        surrounding_walls: bool,
+    ) {
+        if row_end < row_start || col_end < col_start {
+            return;
+        }
 
-        assert_eq!(universe.nodes.len(), grid_w[0].len() * grid_w.len());
+        if surrounding_walls {
+            for row in 0..self.height as i32 {
+                let left_node = *self.get_node_ref(0, row);
+                let right_node = *self.get_node_ref((self.width - 1) as i32, row);
 
-        for y in 0..grid_w.len() {
-            for x in 0..grid_w[y].len() {
-                match grid_w[y][x] {
-                    n if n == w => universe.set_passable(x as i32, y as i32, false),
-                    n if n == h => universe.set_weight(x as i32, y as i32, n as i32),
-                    _ => {}
-                }
+                result.push(left_node);
+                result.push(right_node);
+            }
+
+            for col in 0..self.width as i32 {
+                let top_node = *self.get_node_ref(col, 0);
+                let bottom_node = *self.get_node_ref(col, (self.height - 1) as i32);
+
+                result.push(top_node);
+                result.push(bottom_node);
             }
         }
 
-        let path = universe.astar(0, 0, 4, 3);
-        println!("visited {}", path.processed.len());
-        assert_eq!(path.path.len(), 10);
-    }
+        let mut possible_rows: Vec<i32> = Vec::new();
 
-    #[test]
-    fn recursive_division_maze_works() {
-        let h: i32 = 25;
-        let w: i32 = 62;
-        let universe = Universe::new(w as u32, h as u32);
+        for i in (if orientation == Orientation::Horizontal {
+            row_start
+        } else {
+            row_start - 1
+        }..if orientation == Orientation::Horizontal {
+            row_end + 1
+        } else {
+            row_end + 2
+        })
+            .step_by(2)
+        {
+            possible_rows.push(i);
+        }
 
-        assert_eq!(universe.nodes.len(), (w * h) as usize);
+        let mut possible_cols: Vec<i32> = Vec::new();
 
-        let mut maze: Vec<GridNode> = Vec::new();
-        universe.recursive_division_maze(
-            &mut maze,
-            2,
-            h - 3,
-            2,
-            w - 3,
-            Orientation::Horizontal,
-            true,
-        );
+        for i in (if orientation == Orientation::Horizontal {
+            col_start - 1
+        } else {
+            col_start
+        }..if orientation == Orientation::Horizontal {
+            col_end + 2
+        } else {
+            col_end + 1
+        })
+            .step_by(2)
+        {
+            possible_cols.push(i);
+        }
 
-        assert_eq!(maze.len() > 0, true);
+        let rand_row_index = random_index(rng, possible_rows.len());
+        let rand_col_index = random_index(rng, possible_cols.len());
+
+        let rand_row = possible_rows[rand_row_index];
+        let rand_col = possible_cols[rand_col_index];
+
+        if orientation == Orientation::Horizontal {
+            for col in 0..self.width as i32 {
+                if col != rand_col && col >= col_start - 1 && col <= col_end + 1 {
+                    let node = *self.get_node_ref(col, rand_row);
+                    result.push(node);
+                }
+            }
+
+            if rand_row - 2 - row_start > col_end - col_start {
+                self.recursive_division_maze(
+                    result,
+                    rng,
+                    row_start,
+                    rand_row - 2,
+                    col_start,
+                    col_end,
+                    orientation,
+                    false,
+                );
+            } else {
+                self.recursive_division_maze(
+                    result,
+                    rng,
+                    row_start,
+                    rand_row - 2,
+                    col_start,
+                    col_end,
+                    Orientation::Vertical,
+                    false,
+                );
+            }
+
+            if row_end - (rand_row + 2) > col_end - col_start {
+                self.recursive_division_maze(
+                    result,
+                    rng,
+                    rand_row + 2,
+                    row_end,
+                    col_start,
+                    col_end,
+                    orientation,
+                    false,
+                );
+            } else {
+                self.recursive_division_maze(
+                    result,
+                    rng,
+                    rand_row + 2,
+                    row_end,
+                    col_start,
+                    col_end,
+                    Orientation::Vertical,
+                    false,
+                );
+            }
+        } else {
+            for row in 0..self.height as i32 {
+                if row != rand_row && row >= row_start - 1 && row <= row_end + 1 {
+                    let node = *self.get_node_ref(rand_col, row);
+                    result.push(node);
+                }
+            }
+
+            if row_end - row_start > rand_col - 2 - col_start {
+                self.recursive_division_maze(
+                    result,
+                    rng,
+                    row_start,
+                    row_end,
+                    col_start,
+                    rand_col - 2,
+                    Orientation::Horizontal,
+                    false,
+                );
+            } else {
+                self.recursive_division_maze(
+                    result,
+                    rng,
+                    row_start,
+                    row_end,
+                    col_start,
+                    rand_col - 2,
+                    orientation,
+                    false,
+                );
+            }
+
+            if row_end - row_start > col_end - (rand_col + 2) {
+                self.recursive_division_maze(
+                    result,
+                    rng,
+                    row_start,
+                    row_end,
+                    rand_col + 2,
+                    col_end,
+                    Orientation::Horizontal,
+                    false,
+                );
+            } else {
+                self.recursive_division_maze(
+                    result,
+                    rng,
+                    row_start,
+                    row_end,
+                    rand_col + 2,
+                    col_end,
+                    orientation,
+                    false,
+                );
+            }
+        }
+    }
+
+    pub(super) fn is_passable(&self, x: i32, y: i32) -> bool {
+        return self.has_node(x, y) && self.get_node_ref(x, y).passable;
+    }
+
+    /// Whether a single step from (x, y) by (direction_x, direction_y) is allowed under the current connectivity.
+    pub(super) fn can_step(&self, x: i32, y: i32, direction_x: i32, direction_y: i32) -> bool {
+        if !self.is_passable(x + direction_x, y + direction_y) {
+            return false;
+        }
+
+        if direction_x == 0 || direction_y == 0 {
+            return true;
+        }
+
+        let horizontal_open = self.is_passable(x + direction_x, y);
+        let vertical_open = self.is_passable(x, y + direction_y);
+
+        return match self.connectivity {
+            Connectivity::FourWay => false,
+            Connectivity::EightWay => horizontal_open && vertical_open,
+            Connectivity::EightWayCornerCutting => horizontal_open || vertical_open,
+        };
+    }
+
+    pub(super) fn get_neighbors(&self, x: i32, y: i32) -> Vec<&GridNode> {
+        let mut vec: Vec<&GridNode> = Vec::with_capacity(8);
+
+        for (direction_x, direction_y) in [
+            (0, -1),
+            (0, 1),
+            (-1, 0),
+            (1, 0),
+            (-1, -1),
+            (1, -1),
+            (-1, 1),
+            (1, 1),
+        ] {
+            if self.can_step(x, y, direction_x, direction_y) {
+                vec.push(self.get_node_ref(x + direction_x, y + direction_y));
+            }
+        }
+
+        return vec;
+    }
+
+    pub(super) fn get_neighbor_indices(&self, index: usize) -> Vec<usize> {
+        let node = self.get_node_by_index(index);
+
+        return self
+            .get_neighbors(node.x, node.y)
+            .into_iter()
+            .map(|neighbor| self.get_index(neighbor.x, neighbor.y))
+            .collect();
+    }
+
+    /// Cost of stepping from `from` onto the adjacent node `to`, including the weight of `to`.
+    pub(super) fn get_move_cost(&self, from: &GridNode, to: &GridNode) -> i64 {
+        let step_cost = if from.x != to.x && from.y != to.y {
+            DIAGONAL_COST
+        } else {
+            STRAIGHT_COST
+        };
+
+        return step_cost + self.get_weight_cost(to);
+    }
+
+    pub(super) fn get_weight_cost(&self, node: &GridNode) -> i64 {
+        return node.weight as i64 * STRAIGHT_COST;
+    }
+
+    pub(super) fn heuristic(&self, a: &GridNode, b: &GridNode) -> i64 {
+        let estimate = match self.get_heuristic() {
+            Heuristic::Manhattan => Universe::manhattan_distance(a, b),
+            Heuristic::Euclidean => Universe::euclidean_distance(a, b),
+            Heuristic::Chebyshev => Universe::chebyshev_distance(a, b),
+            Heuristic::Octile => Universe::octile_distance(a, b),
+            Heuristic::Zero => 0,
+        };
+
+        return (estimate as f64 * self.heuristic_weight).round() as i64;
+    }
+
+    fn manhattan_distance(a: &GridNode, b: &GridNode) -> i64 {
+        let distance_x = (a.x as i64 - b.x as i64).abs();
+        let distance_y = (a.y as i64 - b.y as i64).abs();
+
+        return (distance_x + distance_y) * STRAIGHT_COST;
+    }
+
+    fn euclidean_distance(a: &GridNode, b: &GridNode) -> i64 {
+        let distance_x = (a.x - b.x) as f64;
+        let distance_y = (a.y - b.y) as f64;
+
+        return (distance_x.hypot(distance_y) * STRAIGHT_COST as f64).round() as i64;
+    }
+
+    fn chebyshev_distance(a: &GridNode, b: &GridNode) -> i64 {
+        let distance_x = (a.x as i64 - b.x as i64).abs();
+        let distance_y = (a.y as i64 - b.y as i64).abs();
+
+        return distance_x.max(distance_y) * STRAIGHT_COST;
+    }
+
+    pub(super) fn octile_distance(a: &GridNode, b: &GridNode) -> i64 {
+        let distance_x = (a.x as i64 - b.x as i64).abs();
+        let distance_y = (a.y as i64 - b.y as i64).abs();
+        let diagonal_steps = distance_x.min(distance_y);
+
+        return diagonal_steps * DIAGONAL_COST
+            + (distance_x.max(distance_y) - diagonal_steps) * STRAIGHT_COST;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::universe::TraceEvent;
+
+    #[test]
+    #[allow(clippy::needless_range_loop, clippy::unnecessary_cast)]
+    fn astar_works() {
+        let w: i32 = -1;
+        let h = 1000;
+
+        let grid_w = [
+            [0, 0, w, 0, 0],
+            [0, 0, w, 0, 0],
+            [0, 0, w, 0, 0],
+            [0, 0, h, 0, 0],
+            [0, 0, 0, 0, 0],
+        ];
+
+        let mut universe = Universe::new(grid_w[0].len() as u32, grid_w.len() as u32);
+
+        assert_eq!(universe.nodes.len(), grid_w[0].len() * grid_w.len());
+
+        for y in 0..grid_w.len() {
+            for x in 0..grid_w[y].len() {
+                match grid_w[y][x] {
+                    n if n == w => universe.set_passable(x as i32, y as i32, false).unwrap(),
+                    n if n == h => universe.set_weight(x as i32, y as i32, n as i32).unwrap(),
+                    _ => {}
+                }
+            }
+        }
+
+        let path = universe
+            .find_path(0, 0, 4, 3, PathFindingAlgorithm::Astar, false)
+            .unwrap();
+        println!("visited {}", path.processed.len());
+        assert_eq!(path.path.len(), 10);
+        assert_eq!(path.statistics.path_length, 9);
+        assert_eq!(path.statistics.path_cost, 9.0);
+    }
+
+    #[test]
+    fn statistics_are_filled_by_every_algorithm() {
+        let mut universe = Universe::new(10, 8);
+
+        for y in 1..8 {
+            universe.set_passable(4, y, false).unwrap();
+        }
+
+        universe.set_weight(7, 2, 3).unwrap();
+
+        for algorithm in [
+            PathFindingAlgorithm::Astar,
+            PathFindingAlgorithm::AstarBidirectional,
+            PathFindingAlgorithm::Dijkstra,
+            PathFindingAlgorithm::BFS,
+            PathFindingAlgorithm::BFSBidirectional,
+            PathFindingAlgorithm::DFS,
+            PathFindingAlgorithm::GreedyBFS,
+        ] {
+            let result = universe.find_path(0, 7, 9, 7, algorithm, false).unwrap();
+            let statistics = result.statistics;
+            let unique: HashSet<&GridNode> = result.processed.iter().collect();
+
+            assert_eq!(statistics.path_length, result.path.len() - 1);
+            assert!(statistics.path_cost >= statistics.path_length as f64);
+            assert_eq!(statistics.nodes_expanded, result.processed.len());
+            assert!(statistics.nodes_generated >= unique.len());
+            assert!(statistics.peak_frontier_size > 0);
+            assert!(statistics.re_expansions <= statistics.nodes_expanded - unique.len());
+        }
+    }
+
+    #[test]
+    fn statistics_count_diagonal_steps_and_weights() {
+        let mut universe = Universe::new(6, 6);
+        universe.connectivity = Connectivity::EightWay;
+        universe.set_weight(5, 5, 2).unwrap();
+
+        let result = universe
+            .find_path(0, 0, 5, 5, PathFindingAlgorithm::Dijkstra, false)
+            .unwrap();
+
+        assert_eq!(result.statistics.path_length, 5);
+        assert_eq!(result.statistics.path_cost, 9.0710);
+
+        universe.set_weight(5, 5, 0).unwrap();
+
+        let jps = universe
+            .find_path(0, 0, 5, 5, PathFindingAlgorithm::JumpPointSearch, false)
+            .unwrap();
+
+        assert_eq!(jps.statistics.path_cost, 7.0710);
+        assert!(jps.statistics.nodes_expanded < jps.processed.len());
+    }
+
+    #[test]
+    fn huge_weights_do_not_overflow_costs() {
+        let mut universe = Universe::new(3, 1);
+        universe.set_weight(1, 0, 30_000_000).unwrap();
+        universe.set_weight(2, 0, i32::MAX).unwrap();
+
+        for algorithm in [
+            PathFindingAlgorithm::Dijkstra,
+            PathFindingAlgorithm::Astar,
+            PathFindingAlgorithm::GreedyBFS,
+        ] {
+            let result = universe.find_path(0, 0, 2, 0, algorithm, true).unwrap();
+
+            assert_eq!(
+                result.statistics.path_cost,
+                2.0 + 30_000_000.0 + i32::MAX as f64
+            );
+
+            // Trace scores are reported in the same units as the path cost, not in fixed point.
+            let last_g = result
+                .trace
+                .unwrap()
+                .iter()
+                .rev()
+                .find_map(|event| match event {
+                    TraceEvent::Dequeue { g, .. } => *g,
+                    _ => None,
+                });
+            if algorithm == PathFindingAlgorithm::Dijkstra {
+                assert_eq!(last_g, Some(result.statistics.path_cost));
+            }
+        }
+    }
+
+    #[test]
+    fn jump_point_search_matches_astar_path_length() {
+        let mut universe = Universe::from_ascii(
+            "\
+            ........\n\
+            .#####..\n\
+            .....#..\n\
+            ####.#.#\n\
+            ........\n\
+            .#.####.\n\
+            .#....#.\n",
+        )
+        .unwrap()
+        .universe;
+
+        let astar = universe
+            .find_path(0, 0, 5, 6, PathFindingAlgorithm::Astar, false)
+            .unwrap();
+        let jps = universe
+            .find_path(0, 0, 5, 6, PathFindingAlgorithm::JumpPointSearch, false)
+            .unwrap();
+
+        assert_eq!(jps.path.len(), astar.path.len());
+        assert_eq!(jps.path.first(), Some(&universe.get_node(0, 0).unwrap()));
+        assert_eq!(jps.path.last(), Some(&universe.get_node(5, 6).unwrap()));
+
+        for pair in jps.path.windows(2) {
+            assert_eq!(
+                Universe::manhattan_distance(&pair[0], &pair[1]),
+                STRAIGHT_COST
+            );
+            assert!(pair[1].passable);
+        }
+
+        universe.reset();
+
+        let open_astar = universe
+            .find_path(0, 0, 7, 6, PathFindingAlgorithm::Astar, false)
+            .unwrap();
+        let open_jps = universe
+            .find_path(0, 0, 7, 6, PathFindingAlgorithm::JumpPointSearch, false)
+            .unwrap();
+
+        assert_eq!(open_jps.path.len(), open_astar.path.len());
+    }
+
+    #[test]
+    fn jump_point_search_rejects_weighted_grids() {
+        let mut universe = Universe::new(5, 5);
+        universe.set_weight(2, 2, 15).unwrap();
+
+        assert_eq!(
+            universe
+                .find_path(0, 0, 4, 4, PathFindingAlgorithm::JumpPointSearch, false)
+                .unwrap_err()
+                .to_string(),
+            "jump point search only supports unweighted grids"
+        );
+    }
+
+    #[test]
+    fn bad_coordinates_return_errors() {
+        let mut universe = Universe::new(5, 4);
+        universe.set_passable(0, 0, false).unwrap();
+        universe.set_passable(4, 3, false).unwrap();
+
+        let out_of_bounds = universe.set_weight(5, 0, 3).unwrap_err();
+        assert_eq!(out_of_bounds.code(), "outOfBounds");
+        assert_eq!(out_of_bounds.to_string(), "5,0 is outside the 5x4 grid");
+        assert!(universe.get_node(0, -1).is_err());
+
+        let search = |start_x, start_y, end_x, end_y| {
+            return universe
+                .find_path(
+                    start_x,
+                    start_y,
+                    end_x,
+                    end_y,
+                    PathFindingAlgorithm::Astar,
+                    false,
+                )
+                .err()
+                .unwrap();
+        };
+
+        assert_eq!(search(0, 4, 1, 1).code(), "outOfBounds");
+        assert_eq!(
+            search(0, 0, 1, 1),
+            UniverseError::StartBlocked { x: 0, y: 0 }
+        );
+        assert_eq!(
+            search(1, 1, 4, 3),
+            UniverseError::GoalBlocked { x: 4, y: 3 }
+        );
+    }
+
+    #[test]
+    fn jump_point_search_returns_empty_path_when_unreachable() {
+        let mut universe = Universe::new(5, 5);
+
+        for y in 0..5 {
+            universe.set_passable(2, y, false).unwrap();
+        }
+
+        let result = universe
+            .find_path(0, 0, 4, 4, PathFindingAlgorithm::JumpPointSearch, false)
+            .unwrap();
+
+        assert!(result.path.is_empty());
+        assert!(!result.processed.is_empty());
+    }
+
+    #[test]
+    fn eight_way_moves_diagonally() {
+        let mut universe = Universe::new(6, 6);
+        universe.connectivity = Connectivity::EightWay;
+
+        let path = universe
+            .find_path(0, 0, 5, 5, PathFindingAlgorithm::Astar, false)
+            .unwrap();
+
+        assert_eq!(path.path.len(), 6);
+        assert_eq!(
+            Universe::octile_distance(
+                &universe.get_node(0, 0).unwrap(),
+                &universe.get_node(5, 5).unwrap()
+            ),
+            5 * DIAGONAL_COST
+        );
+    }
+
+    #[test]
+    fn corner_cutting_follows_connectivity() {
+        // A single wall at (1, 0) sits on the corner of the diagonal step (0, 0) -> (1, 1).
+        let mut universe = Universe::new(3, 3);
+        universe.set_passable(1, 0, false).unwrap();
+
+        universe.connectivity = Connectivity::FourWay;
+        assert!(!universe.can_step(0, 0, 1, 1));
+
+        universe.connectivity = Connectivity::EightWay;
+        assert!(!universe.can_step(0, 0, 1, 1));
+        assert_eq!(
+            universe
+                .find_path(0, 0, 2, 0, PathFindingAlgorithm::Dijkstra, false)
+                .unwrap()
+                .path
+                .len(),
+            5
+        );
+
+        universe.connectivity = Connectivity::EightWayCornerCutting;
+        assert!(universe.can_step(0, 0, 1, 1));
+        assert_eq!(
+            universe
+                .find_path(0, 0, 2, 0, PathFindingAlgorithm::Dijkstra, false)
+                .unwrap()
+                .path
+                .len(),
+            3
+        );
+
+        // Two diagonal walls block the gap regardless of corner cutting.
+        universe.set_passable(0, 1, false).unwrap();
+        assert!(!universe.can_step(0, 0, 1, 1));
+    }
+
+    #[test]
+    fn jump_point_search_matches_dijkstra_cost_with_diagonals() {
+        let w: i32 = -1;
+
+        let grid_w = [
+            [0, 0, 0, 0, 0, 0, 0, 0],
+            [0, w, w, w, w, w, 0, 0],
+            [0, 0, 0, 0, 0, w, 0, 0],
+            [w, w, w, w, 0, w, 0, w],
+            [0, 0, 0, 0, 0, 0, 0, 0],
+            [0, w, 0, w, w, w, w, 0],
+            [0, w, 0, 0, 0, 0, w, 0],
+        ];
+
+        let mut universe = Universe::new(grid_w[0].len() as u32, grid_w.len() as u32);
+
+        for (y, row) in grid_w.iter().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                if *cell == w {
+                    universe.set_passable(x as i32, y as i32, false).unwrap();
+                }
+            }
+        }
+
+        for connectivity in [Connectivity::EightWay, Connectivity::EightWayCornerCutting] {
+            universe.connectivity = connectivity;
+
+            let dijkstra = universe
+                .find_path(0, 0, 5, 6, PathFindingAlgorithm::Dijkstra, false)
+                .unwrap();
+            let jps = universe
+                .find_path(0, 0, 5, 6, PathFindingAlgorithm::JumpPointSearch, false)
+                .unwrap();
+
+            assert!(!jps.path.is_empty());
+            assert_eq!(jps.statistics.path_cost, dijkstra.statistics.path_cost);
+
+            let unique: HashSet<&GridNode> = jps.processed.iter().collect();
+            assert_eq!(unique.len(), jps.processed.len());
+
+            for pair in jps.path.windows(2) {
+                let (from, to) = (pair[0], pair[1]);
+                assert!(universe.can_step(from.x, from.y, to.x - from.x, to.y - from.y));
+            }
+        }
+    }
+
+    #[test]
+    fn heuristic_follows_connectivity_until_set() {
+        let mut universe = Universe::new(5, 5);
+
+        assert_eq!(universe.get_heuristic(), Heuristic::Manhattan);
+
+        universe.connectivity = Connectivity::EightWay;
+        assert_eq!(universe.get_heuristic(), Heuristic::Octile);
+
+        universe.set_heuristic(Some(Heuristic::Euclidean));
+        assert_eq!(universe.get_heuristic(), Heuristic::Euclidean);
+
+        universe.set_heuristic(None);
+        assert_eq!(universe.get_heuristic(), Heuristic::Octile);
+    }
+
+    #[test]
+    fn admissible_heuristics_find_optimal_paths() {
+        let mut universe = Universe::new(20, 20);
+        universe.connectivity = Connectivity::EightWay;
+
+        for y in 2..18 {
+            universe.set_passable(10, y, false).unwrap();
+        }
+
+        universe.set_weight(4, 4, 5).unwrap();
+
+        let optimal_cost = universe
+            .find_path(0, 10, 19, 10, PathFindingAlgorithm::Dijkstra, false)
+            .unwrap()
+            .statistics
+            .path_cost;
+
+        for heuristic in [
+            Heuristic::Euclidean,
+            Heuristic::Chebyshev,
+            Heuristic::Octile,
+            Heuristic::Zero,
+        ] {
+            universe.set_heuristic(Some(heuristic));
+
+            let astar = universe
+                .find_path(0, 10, 19, 10, PathFindingAlgorithm::Astar, false)
+                .unwrap();
+
+            assert_eq!(astar.statistics.path_cost, optimal_cost);
+        }
+    }
+
+    #[test]
+    fn weighted_heuristic_expands_fewer_nodes() {
+        let mut universe = Universe::new(30, 30);
+
+        for x in 5..25 {
+            universe.set_passable(x, 15, false).unwrap();
+        }
+
+        universe.set_heuristic(Some(Heuristic::Zero));
+        let zero = universe
+            .find_path(15, 0, 15, 29, PathFindingAlgorithm::Astar, false)
+            .unwrap();
+
+        universe.set_heuristic(Some(Heuristic::Manhattan));
+        let manhattan = universe
+            .find_path(15, 0, 15, 29, PathFindingAlgorithm::Astar, false)
+            .unwrap();
+
+        universe.heuristic_weight = 5.0;
+        let weighted = universe
+            .find_path(15, 0, 15, 29, PathFindingAlgorithm::Astar, false)
+            .unwrap();
+
+        assert!(manhattan.processed.len() < zero.processed.len());
+        assert!(weighted.processed.len() < manhattan.processed.len());
+        assert!(weighted.path.len() >= manhattan.path.len());
+    }
+
+    #[test]
+    #[allow(clippy::bool_assert_comparison, clippy::len_zero)]
+    fn recursive_division_maze_works() {
+        let h: i32 = 25;
+        let w: i32 = 62;
+        let universe = Universe::new(w as u32, h as u32);
+
+        assert_eq!(universe.nodes.len(), (w * h) as usize);
+
+        let mut maze: Vec<GridNode> = Vec::new();
+        universe.recursive_division_maze(
+            &mut maze,
+            &mut ChaCha8Rng::seed_from_u64(0),
+            2,
+            h - 3,
+            2,
+            w - 3,
+            Orientation::Horizontal,
+            true,
+        );
+
+        assert_eq!(maze.len() > 0, true);
+    }
+
+    #[test]
+    fn load_snapshot_restores_grid() {
+        let mut source = Universe::new(6, 4);
+        source.set_passable(1, 2, false).unwrap();
+        source.set_passable(5, 3, false).unwrap();
+        source.set_weight(3, 0, 7).unwrap();
+        source.set_weight(0, 3, 2).unwrap();
+
+        let mut universe = Universe::new(2, 2);
+        universe.load_snapshot(&source.get_snapshot()).unwrap();
+
+        assert_eq!(universe.width, 6);
+        assert_eq!(universe.height, 4);
+        assert_eq!(universe.nodes, source.nodes);
+    }
+
+    #[test]
+    fn load_snapshot_rejects_invalid_coordinates() {
+        let mut universe = Universe::new(3, 3);
+        universe.set_passable(1, 1, false).unwrap();
+
+        let snapshot = GridSnapshot {
+            version: SNAPSHOT_VERSION,
+            width: 4,
+            height: 4,
+            weight: None,
+            walls: vec!["0,0".to_string(), "4,1".to_string()],
+            weights: Vec::new(),
+        };
+        let err = universe.load_snapshot(&snapshot).unwrap_err();
+        assert_eq!(err.to_string(), "wall at 4,1 is outside the 4x4 grid");
+
+        let snapshot = GridSnapshot {
+            version: SNAPSHOT_VERSION,
+            width: 4,
+            height: 4,
+            weight: None,
+            walls: Vec::new(),
+            weights: vec!["a,b".to_string()],
+        };
+        let err = universe.load_snapshot(&snapshot).unwrap_err();
+        assert_eq!(err.to_string(), "malformed weight entry \"a,b\"");
+
+        assert_eq!(universe.width, 3);
+        assert!(!universe.get_node(1, 1).unwrap().passable);
+    }
+
+    #[test]
+    fn load_snapshot_rejects_oversized_grids() {
+        let mut universe = Universe::new(3, 3);
+
+        let mut snapshot = GridSnapshot {
+            version: SNAPSHOT_VERSION,
+            width: 100_000,
+            height: 100_000,
+            weight: None,
+            walls: Vec::new(),
+            weights: Vec::new(),
+        };
+        let err = universe.load_snapshot(&snapshot).unwrap_err();
+        assert_eq!(err.to_string(), "invalid snapshot dimensions 100000x100000");
+
+        snapshot.width = 2;
+        snapshot.height = 1;
+        snapshot.walls = vec!["0,0".to_string(); 3];
+        let err = universe.load_snapshot(&snapshot).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "snapshot lists more cells than its 2x1 grid holds"
+        );
+
+        assert_eq!(universe.width, 3);
+    }
+
+    #[test]
+    fn load_snapshot_reads_legacy_format() {
+        let snapshot: GridSnapshot = serde_json::from_str(
+            r#"{"width":3,"height":2,"weight":4,"walls":["1,0"],"weights":["0,1","2,1"]}"#,
+        )
+        .unwrap();
+        assert_eq!(snapshot.version, LEGACY_SNAPSHOT_VERSION);
+
+        let mut universe = Universe::new(1, 1);
+        universe.load_snapshot(&snapshot).unwrap();
+
+        assert!(!universe.get_node(1, 0).unwrap().passable);
+        assert_eq!(universe.get_node(0, 1).unwrap().weight, 4);
+        assert_eq!(universe.get_node(2, 1).unwrap().weight, 4);
+        assert_eq!(universe.get_node(1, 1).unwrap().weight, 0);
+    }
+
+    fn maze_rows(universe: &Universe, maze: &[GridNode]) -> Vec<String> {
+        let walls: HashSet<(i32, i32)> = maze.iter().map(|node| (node.x, node.y)).collect();
+
+        return (0..universe.height as i32)
+            .map(|y| {
+                (0..universe.width as i32)
+                    .map(|x| if walls.contains(&(x, y)) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect();
+    }
+
+    #[test]
+    fn seeded_mazes_are_reproducible() {
+        let universe = Universe::new(11, 9);
+
+        let maze = universe
+            .generate_maze(
+                MazeType::RecursiveDivision,
+                Some(42),
+                &MazeOptions::default(),
+            )
+            .unwrap();
+
+        assert_eq!(
+            maze_rows(&universe, &maze),
+            [
+                "###########",
+                "#.........#",
+                "#######.###",
+                "#.#.#.....#",
+                "#.#.###.#.#",
+                "#.......#.#",
+                "###.#.#.#.#",
+                "#...#.#.#.#",
+                "###########",
+            ]
+        );
+        assert_eq!(
+            universe
+                .generate_maze(
+                    MazeType::RecursiveDivision,
+                    Some(42),
+                    &MazeOptions::default()
+                )
+                .unwrap(),
+            maze
+        );
+        assert_ne!(
+            universe
+                .generate_maze(
+                    MazeType::RecursiveDivision,
+                    Some(43),
+                    &MazeOptions::default()
+                )
+                .unwrap(),
+            maze
+        );
+
+        assert_eq!(
+            universe
+                .generate_maze(MazeType::Random25, Some(7), &MazeOptions::default())
+                .unwrap(),
+            universe
+                .generate_maze(MazeType::Random25, Some(7), &MazeOptions::default())
+                .unwrap()
+        );
+    }
+
+    /// Returns a copy of `universe` with the walls of `maze` applied.
+    fn with_maze_walls(universe: &Universe, maze: &[GridNode]) -> Universe {
+        let mut walled = universe.clone();
+
+        for node in maze.iter().filter(|node| !node.passable) {
+            walled.set_passable(node.x, node.y, false).unwrap();
+        }
+
+        return walled;
+    }
+
+    /// Counts the open cells reachable from `start` through four-way steps, including `start` itself.
+    fn reachable_open_cells(universe: &Universe, start: Point) -> usize {
+        let mut reached: HashSet<(i32, i32)> = HashSet::from([(start.x, start.y)]);
+        let mut stack = vec![(start.x, start.y)];
+
+        while let Some((x, y)) = stack.pop() {
+            for (nx, ny) in [(x, y - 1), (x, y + 1), (x - 1, y), (x + 1, y)] {
+                if universe.is_passable(nx, ny) && reached.insert((nx, ny)) {
+                    stack.push((nx, ny));
+                }
+            }
+        }
+
+        return reached.len();
+    }
+
+    /// Checks that the walls leave a spanning tree over the odd-coordinate lattice: every cell reachable and exactly one
+    /// passage fewer than there are cells.
+    fn assert_perfect_maze(universe: &Universe, maze: &[GridNode]) {
+        let cells = ((universe.width as usize - 1) / 2) * ((universe.height as usize - 1) / 2);
+        let open = (universe.width * universe.height) as usize - maze.len();
+
+        assert_eq!(open, cells * 2 - 1);
+        assert_eq!(
+            reachable_open_cells(&with_maze_walls(universe, maze), Point { x: 1, y: 1 }),
+            open
+        );
+    }
+
+    #[test]
+    fn lattice_mazes_are_perfect() {
+        for maze_type in [
+            MazeType::Prim,
+            MazeType::Kruskal,
+            MazeType::Wilson,
+            MazeType::AldousBroder,
+            MazeType::RecursiveBacktracker,
+            MazeType::HuntAndKill,
+        ] {
+            for (width, height) in [(21, 15), (20, 14), (3, 3)] {
+                let universe = Universe::new(width, height);
+
+                for seed in 0..5 {
+                    assert_perfect_maze(
+                        &universe,
+                        &universe
+                            .generate_maze(maze_type, Some(seed), &MazeOptions::default())
+                            .unwrap(),
+                    );
+                }
+            }
+
+            let universe = Universe::new(2, 2);
+            assert_eq!(
+                universe
+                    .generate_maze(maze_type, Some(0), &MazeOptions::default())
+                    .unwrap()
+                    .len(),
+                4
+            );
+        }
+    }
+
+    #[test]
+    fn recursive_backtracker_handles_large_grids() {
+        let universe = Universe::new(501, 501);
+        let maze = universe
+            .generate_maze(
+                MazeType::RecursiveBacktracker,
+                Some(1),
+                &MazeOptions::default(),
+            )
+            .unwrap();
+
+        assert_perfect_maze(&universe, &maze);
+    }
+
+    #[test]
+    fn caves_leave_one_connected_region() {
+        let universe = Universe::new(60, 40);
+
+        for seed in 0..5 {
+            let maze = universe
+                .generate_maze(MazeType::Caves, Some(seed), &MazeOptions::default())
+                .unwrap();
+            let walled = with_maze_walls(&universe, &maze);
+            let open: Vec<&GridNode> = walled.nodes.iter().filter(|node| node.passable).collect();
+
+            assert!(open.len() > universe.nodes.len() / 4);
+            let start = Point {
+                x: open[0].x,
+                y: open[0].y,
+            };
+            assert_eq!(reachable_open_cells(&walled, start), open.len());
+        }
+
+        let options = MazeOptions {
+            cave_fill: 101,
+            ..MazeOptions::default()
+        };
+        assert_eq!(
+            universe
+                .generate_maze(MazeType::Caves, Some(0), &options)
+                .unwrap_err()
+                .to_string(),
+            "cave fill must be a percentage, got 101"
+        );
+
+        let options = MazeOptions {
+            cave_passes: 1_000_000,
+            ..MazeOptions::default()
+        };
+        assert_eq!(
+            universe
+                .generate_maze(MazeType::Caves, Some(0), &options)
+                .unwrap_err()
+                .to_string(),
+            "cave passes must be between 0 and 10, got 1000000"
+        );
+    }
+
+    #[test]
+    fn terrain_weights_are_coherent() {
+        let mut universe = Universe::new(64, 48);
+        universe.set_weight(3, 4, 99).unwrap();
+        universe.set_passable(3, 4, false).unwrap();
+        let wall = universe.get_node(3, 4).unwrap();
+
+        let options = TerrainOptions {
+            min_weight: 2,
+            max_weight: 30,
+            ..TerrainOptions::default()
+        };
+        let terrain = universe.generate_terrain(Some(9), &options).unwrap();
+
+        assert_eq!(
+            terrain,
+            universe.generate_terrain(Some(9), &options).unwrap()
+        );
+        assert_eq!(terrain.len(), universe.nodes.len());
+        assert_eq!(terrain[universe.get_index(3, 4)], wall);
+        assert!(terrain
+            .iter()
+            .filter(|node| node.passable)
+            .all(|node| (2..=30).contains(&node.weight)));
+
+        // Neighboring nodes should differ far less than the full weight range, unlike independent random weights.
+        let steps: Vec<i32> = terrain
+            .windows(2)
+            .filter(|pair| pair[0].y == pair[1].y)
+            .map(|pair| (pair[0].weight - pair[1].weight).abs())
+            .collect();
+        let average_step = steps.iter().sum::<i32>() as f64 / steps.len() as f64;
+
+        assert!(average_step < 2.0);
+        assert!(
+            terrain.iter().map(|node| node.weight).max()
+                > terrain.iter().map(|node| node.weight).min()
+        );
+
+        let options = TerrainOptions {
+            octaves: 0,
+            ..TerrainOptions::default()
+        };
+        assert_eq!(
+            universe
+                .generate_terrain(Some(9), &options)
+                .unwrap_err()
+                .to_string(),
+            "terrain octaves must be between 1 and 16, got 0"
+        );
+    }
+
+    #[test]
+    fn braid_removes_dead_ends() {
+        let universe = Universe::new(31, 21);
+        let dead_ends = |maze: &[GridNode]| {
+            let walls: HashSet<(i32, i32)> = maze.iter().map(|node| (node.x, node.y)).collect();
+            let is_open = |x: i32, y: i32| universe.has_node(x, y) && !walls.contains(&(x, y));
+
+            return universe
+                .nodes
+                .iter()
+                .filter(|node| {
+                    is_open(node.x, node.y)
+                        && [(0, -1), (0, 1), (-1, 0), (1, 0)]
+                            .iter()
+                            .filter(|(dx, dy)| is_open(node.x + dx, node.y + dy))
+                            .count()
+                            == 1
+                })
+                .count();
+        };
+
+        let perfect = universe
+            .generate_maze(MazeType::Prim, Some(3), &MazeOptions::default())
+            .unwrap();
+        let half = MazeOptions {
+            braid: 0.5,
+            ..MazeOptions::default()
+        };
+        let full = MazeOptions {
+            braid: 1.0,
+            ..MazeOptions::default()
+        };
+        let half_braided = universe
+            .generate_maze(MazeType::Prim, Some(3), &half)
+            .unwrap();
+        let fully_braided = universe
+            .generate_maze(MazeType::Prim, Some(3), &full)
+            .unwrap();
+
+        assert!(dead_ends(&perfect) > 0);
+        assert!(dead_ends(&half_braided) <= dead_ends(&perfect) / 2 + 1);
+        assert!(dead_ends(&half_braided) > 0);
+        assert_eq!(dead_ends(&fully_braided), 0);
+        assert!(half_braided.iter().all(|node| perfect.contains(node)));
+
+        let invalid = MazeOptions {
+            braid: 1.5,
+            ..MazeOptions::default()
+        };
+        assert_eq!(
+            universe
+                .generate_maze(MazeType::Prim, Some(3), &invalid)
+                .unwrap_err()
+                .to_string(),
+            "braid factor must be between 0 and 1, got 1.5"
+        );
+    }
+
+    #[test]
+    fn dungeon_rooms_are_connected() {
+        let universe = Universe::new(60, 40);
+        let options = MazeOptions {
+            doorway_weight: 5,
+            ..MazeOptions::default()
+        };
+
+        for seed in 0..5 {
+            let maze = universe
+                .generate_maze(MazeType::Dungeon, Some(seed), &options)
+                .unwrap();
+            let walls: HashSet<(i32, i32)> = maze
+                .iter()
+                .filter(|node| !node.passable)
+                .map(|node| (node.x, node.y))
+                .collect();
+            let doorways: Vec<&GridNode> = maze.iter().filter(|node| node.passable).collect();
+
+            assert!(!doorways.is_empty());
+            assert!(doorways
+                .iter()
+                .all(|node| node.weight == 5 && !walls.contains(&(node.x, node.y))));
+
+            let walled = with_maze_walls(&universe, &maze);
+            let open: Vec<&GridNode> = walled.nodes.iter().filter(|node| node.passable).collect();
+
+            let start = Point {
+                x: open[0].x,
+                y: open[0].y,
+            };
+            assert_eq!(reachable_open_cells(&walled, start), open.len());
+            assert!(open.iter().all(|node| node.x > 0 && node.y > 0));
+        }
+
+        let unweighted = universe
+            .generate_maze(MazeType::Dungeon, Some(0), &MazeOptions::default())
+            .unwrap();
+        assert!(unweighted.iter().all(|node| !node.passable));
+    }
+
+    #[test]
+    fn apply_maze_returns_changed_nodes() {
+        let mut universe = Universe::new(21, 15);
+        let options = MazeOptions::default();
+        let maze = universe
+            .generate_maze(MazeType::Prim, Some(4), &options)
+            .unwrap();
+
+        universe.set_passable(0, 0, false).unwrap();
+        universe.set_weight(1, 1, 3).unwrap();
+        universe.set_weight(2, 0, 6).unwrap();
+
+        let diff = universe
+            .apply_maze(MazeType::Prim, Some(4), &options)
+            .unwrap();
+
+        assert_eq!(diff.len(), maze.len() - 1);
+        assert!(diff.iter().all(|node| !node.passable && node.weight == 0));
+        assert_eq!(universe.get_node(2, 0).unwrap().weight, 0);
+        assert!(universe.get_node(1, 1).unwrap().passable);
+        assert_eq!(universe.get_node(1, 1).unwrap().weight, 3);
+        assert!(maze
+            .iter()
+            .all(|node| !universe.get_node(node.x, node.y).unwrap().passable));
+
+        let clear = MazeOptions {
+            clear: true,
+            ..MazeOptions::default()
+        };
+        let diff = universe
+            .apply_maze(MazeType::Prim, Some(5), &clear)
+            .unwrap();
+        let open = universe.nodes.iter().filter(|node| node.passable).count();
+
+        assert!(diff.iter().any(|node| node.passable));
+        assert!(universe.nodes.iter().all(|node| node.weight == 0));
+        assert_eq!(open, 10 * 7 * 2 - 1);
+    }
+
+    #[test]
+    fn maze_events_replay_to_final_maze() {
+        let universe = Universe::new(25, 19);
+        let options = MazeOptions {
+            braid: 0.5,
+            doorway_weight: 2,
+            ..MazeOptions::default()
+        };
+
+        for maze_type in [
+            MazeType::Random50,
+            MazeType::RecursiveDivision,
+            MazeType::Prim,
+            MazeType::Kruskal,
+            MazeType::Wilson,
+            MazeType::AldousBroder,
+            MazeType::RecursiveBacktracker,
+            MazeType::HuntAndKill,
+            MazeType::Caves,
+            MazeType::Dungeon,
+        ] {
+            let construction = universe
+                .generate_maze_construction(maze_type, Some(11), &options)
+                .unwrap();
+            let mut walls = vec![construction.starts_filled; universe.nodes.len()];
+
+            for event in &construction.events {
+                match *event {
+                    MazeEvent::WallAdded { node } => {
+                        assert!(!walls[universe.get_index(node.x, node.y)]);
+                        walls[universe.get_index(node.x, node.y)] = true;
+                    }
+                    MazeEvent::WallRemoved { node } | MazeEvent::CellCarved { node } => {
+                        assert!(walls[universe.get_index(node.x, node.y)]);
+                        walls[universe.get_index(node.x, node.y)] = false;
+                    }
+                }
+            }
+
+            let expected: HashSet<(i32, i32)> = construction
+                .maze
+                .iter()
+                .filter(|node| !node.passable)
+                .map(|node| (node.x, node.y))
+                .collect();
+            let replayed: HashSet<(i32, i32)> = universe
+                .nodes
+                .iter()
+                .filter(|node| walls[universe.get_index(node.x, node.y)])
+                .map(|node| (node.x, node.y))
+                .collect();
+
+            assert!(!construction.events.is_empty());
+            assert_eq!(replayed, expected, "{:?}", maze_type);
+            assert_eq!(
+                construction.maze,
+                universe
+                    .generate_maze(maze_type, Some(11), &options)
+                    .unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn mazes_connect_start_and_end() {
+        let start = Point { x: 0, y: 0 };
+        let end = Point { x: 24, y: 18 };
+        let options = MazeOptions {
+            start: Some(start),
+            end: Some(end),
+            ..MazeOptions::default()
+        };
+
+        for maze_type in [
+            MazeType::Random75,
+            MazeType::RecursiveDivision,
+            MazeType::Prim,
+            MazeType::Caves,
+            MazeType::Dungeon,
+        ] {
+            for seed in 0..5 {
+                let mut universe = Universe::new(25, 19);
+                universe.set_passable(12, 9, false).unwrap();
+
+                let maze = universe
+                    .generate_maze(maze_type, Some(seed), &options)
+                    .unwrap();
+
+                assert!(maze
+                    .iter()
+                    .all(|node| (node.x, node.y) != (start.x, start.y)
+                        && (node.x, node.y) != (end.x, end.y)));
+
+                universe
+                    .apply_maze(maze_type, Some(seed), &options)
+                    .unwrap();
+
+                let result = universe
+                    .find_path(
+                        start.x,
+                        start.y,
+                        end.x,
+                        end.y,
+                        PathFindingAlgorithm::BFS,
+                        false,
+                    )
+                    .unwrap();
+
+                assert!(!result.path.is_empty(), "{:?} seed {}", maze_type, seed);
+                assert!(!universe.get_node(12, 9).unwrap().passable);
+            }
+        }
+
+        let universe = Universe::new(25, 19);
+        let outside = MazeOptions {
+            start: Some(start),
+            end: Some(Point { x: 25, y: 0 }),
+            ..MazeOptions::default()
+        };
+        assert_eq!(
+            universe
+                .generate_maze(MazeType::Prim, Some(0), &outside)
+                .unwrap_err()
+                .to_string(),
+            "maze end 25,0 is outside the 25x19 grid"
+        );
+
+        let missing_end = MazeOptions {
+            start: Some(start),
+            ..MazeOptions::default()
+        };
+        assert_eq!(
+            universe
+                .generate_maze(MazeType::Prim, Some(0), &missing_end)
+                .unwrap_err()
+                .to_string(),
+            "maze start and end must be given together"
+        );
     }
 }
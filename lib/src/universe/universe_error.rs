@@ -0,0 +1,67 @@
+use std::fmt;
+
+/// Everything that can go wrong in the universe module. Each variant has a stable `code` to branch on, and
+/// `Display` gives a human readable message.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UniverseError {
+    OutOfBounds {
+        x: i32,
+        y: i32,
+        width: u32,
+        height: u32,
+    },
+    StartBlocked {
+        x: i32,
+        y: i32,
+    },
+    GoalBlocked {
+        x: i32,
+        y: i32,
+    },
+    /// The algorithm cannot run on this grid, such as jump point search on a weighted grid.
+    UnsupportedSearch(String),
+    InvalidOptions(String),
+    InvalidSnapshot(String),
+    InvalidMap(String),
+    InvalidScenario(String),
+    InvalidAscii(String),
+}
+
+impl UniverseError {
+    pub fn code(&self) -> &'static str {
+        return match self {
+            UniverseError::OutOfBounds { .. } => "outOfBounds",
+            UniverseError::StartBlocked { .. } => "startBlocked",
+            UniverseError::GoalBlocked { .. } => "goalBlocked",
+            UniverseError::UnsupportedSearch(_) => "unsupportedSearch",
+            UniverseError::InvalidOptions(_) => "invalidOptions",
+            UniverseError::InvalidSnapshot(_) => "invalidSnapshot",
+            UniverseError::InvalidMap(_) => "invalidMap",
+            UniverseError::InvalidScenario(_) => "invalidScenario",
+            UniverseError::InvalidAscii(_) => "invalidAscii",
+        };
+    }
+}
+
+impl fmt::Display for UniverseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            UniverseError::OutOfBounds {
+                x,
+                y,
+                width,
+                height,
+            } => write!(f, "{},{} is outside the {}x{} grid", x, y, width, height),
+            UniverseError::StartBlocked { x, y } => write!(f, "start {},{} is a wall", x, y),
+            UniverseError::GoalBlocked { x, y } => write!(f, "goal {},{} is a wall", x, y),
+            UniverseError::UnsupportedSearch(message)
+            | UniverseError::InvalidOptions(message)
+            | UniverseError::InvalidSnapshot(message)
+            | UniverseError::InvalidMap(message)
+            | UniverseError::InvalidScenario(message)
+            | UniverseError::InvalidAscii(message) => f.write_str(message),
+        };
+    }
+}
+
+impl std::error::Error for UniverseError {}
@@ -0,0 +1,82 @@
+/// Fractal value noise: pseudo-random values on an integer lattice, smoothly interpolated and summed over octaves
+/// that each double the frequency of the last.
+pub struct ValueNoise {
+    seed: u32,
+    octaves: u32,
+    persistence: f64,
+}
+
+impl ValueNoise {
+    pub fn new(seed: u32, octaves: u32, persistence: f64) -> Self {
+        return ValueNoise {
+            seed,
+            octaves,
+            persistence,
+        };
+    }
+
+    /// Noise at a point, between 0 and 1.
+    pub fn sample(&self, x: f64, y: f64) -> f64 {
+        let mut total = 0.0;
+        let mut amplitude = 1.0;
+        let mut amplitude_sum = 0.0;
+        let mut frequency = 1.0;
+
+        for octave in 0..self.octaves {
+            total += self.smooth_sample(x * frequency, y * frequency, octave) * amplitude;
+            amplitude_sum += amplitude;
+            amplitude *= self.persistence;
+            frequency *= 2.0;
+        }
+
+        return total / amplitude_sum;
+    }
+
+    fn smooth_sample(&self, x: f64, y: f64, octave: u32) -> f64 {
+        let x0 = x.floor();
+        let y0 = y.floor();
+        let tx = smoothstep(x - x0);
+        let ty = smoothstep(y - y0);
+        let (x0, y0) = (x0 as i32, y0 as i32);
+
+        let top = lerp(
+            self.lattice_value(x0, y0, octave),
+            self.lattice_value(x0 + 1, y0, octave),
+            tx,
+        );
+        let bottom = lerp(
+            self.lattice_value(x0, y0 + 1, octave),
+            self.lattice_value(x0 + 1, y0 + 1, octave),
+            tx,
+        );
+
+        return lerp(top, bottom, ty);
+    }
+
+    fn lattice_value(&self, x: i32, y: i32, octave: u32) -> f64 {
+        let mut hash = self.seed ^ octave.wrapping_mul(0x9e37_79b9);
+        hash = mix(hash ^ x as u32);
+        hash = mix(hash ^ y as u32);
+
+        return hash as f64 / u32::MAX as f64;
+    }
+}
+
+/// Integer finalizer from MurmurHash3, so lattice values only depend on integer arithmetic and match on every target.
+fn mix(mut hash: u32) -> u32 {
+    hash ^= hash >> 16;
+    hash = hash.wrapping_mul(0x85eb_ca6b);
+    hash ^= hash >> 13;
+    hash = hash.wrapping_mul(0xc2b2_ae35);
+    hash ^= hash >> 16;
+
+    return hash;
+}
+
+fn smoothstep(t: f64) -> f64 {
+    return t * t * (3.0 - 2.0 * t);
+}
+
+fn lerp(a: f64, b: f64, t: f64) -> f64 {
+    return a + (b - a) * t;
+}
@@ -0,0 +1,71 @@
+use serde::Deserialize;
+use wasm_bindgen::{prelude::wasm_bindgen, JsCast};
+
+use crate::universe::{
+    AsciiGrid, AsciiOverlay, GridNode, PathResult, Point, SearchStatistics, Universe, UniverseError,
+};
+
+use super::{IAsciiOverlay, IPoint};
+
+#[wasm_bindgen]
+impl AsciiGrid {
+    #[wasm_bindgen(getter = universe)]
+    pub fn universe_js(&self) -> Universe {
+        return self.universe.clone();
+    }
+
+    #[wasm_bindgen(getter = start)]
+    pub fn start_js(&self) -> Option<IPoint> {
+        return self.start.map(point_to_js);
+    }
+
+    #[wasm_bindgen(getter = end)]
+    pub fn end_js(&self) -> Option<IPoint> {
+        return self.end.map(point_to_js);
+    }
+}
+
+/// The JS shape of an `AsciiOverlay`, holding its own copy of the nodes to draw.
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub(super) struct JsAsciiOverlay {
+    start: Option<Point>,
+    end: Option<Point>,
+    result: Option<JsOverlayResult>,
+}
+
+#[derive(Deserialize)]
+struct JsOverlayResult {
+    path: Vec<GridNode>,
+    processed: Vec<GridNode>,
+}
+
+impl JsAsciiOverlay {
+    pub(super) fn parse(overlay: IAsciiOverlay) -> Result<JsAsciiOverlay, UniverseError> {
+        return serde_wasm_bindgen::from_value(overlay.into())
+            .map_err(|err| UniverseError::InvalidAscii(format!("invalid ascii overlay: {}", err)));
+    }
+
+    pub(super) fn path_result(&self) -> Option<PathResult> {
+        return self.result.as_ref().map(|result| PathResult {
+            path: result.path.clone(),
+            processed: result.processed.clone(),
+            statistics: SearchStatistics::default(),
+            trace: None,
+        });
+    }
+
+    pub(super) fn overlay<'a>(&self, result: Option<&'a PathResult>) -> AsciiOverlay<'a> {
+        return AsciiOverlay {
+            start: self.start,
+            end: self.end,
+            result,
+        };
+    }
+}
+
+fn point_to_js(point: Point) -> IPoint {
+    return serde_wasm_bindgen::to_value(&point)
+        .unwrap()
+        .unchecked_into();
+}
@@ -0,0 +1,207 @@
+//! The JS bindings, built with the `wasm` feature. They convert between the core types and the
+//! TypeScript shapes declared below, and surface every `UniverseError` as a thrown `IUniverseError`.
+
+use wasm_bindgen::prelude::wasm_bindgen;
+
+mod ascii;
+mod moving_ai;
+mod search_session;
+mod universe;
+mod universe_error;
+
+#[wasm_bindgen(typescript_custom_section)]
+const IGRID_NODE: &'static str = r#"
+interface IGridNode {
+    x: number;
+    y: number;
+    weight: number;
+    passable: boolean;
+}
+"#;
+
+#[wasm_bindgen(typescript_custom_section)]
+const IPATH_RESPONSE: &'static str = r#"
+interface IPathResponse {
+    path: IGridNode[];
+    processed: IGridNode[];
+    statistics: ISearchStatistics;
+    trace?: ITraceEvent[];
+}
+"#;
+
+#[wasm_bindgen(typescript_custom_section)]
+const ISEARCH_STATISTICS: &'static str = r#"
+interface ISearchStatistics {
+    pathCost: number;
+    pathLength: number;
+    nodesExpanded: number;
+    nodesGenerated: number;
+    peakFrontierSize: number;
+    reExpansions: number;
+}
+"#;
+
+#[wasm_bindgen(typescript_custom_section)]
+const ITRACE_EVENT: &'static str = r#"
+type TraceDirection = 'forward' | 'backward';
+
+/** Scores are in the same units as `ISearchStatistics.pathCost`. */
+type ITraceEvent =
+    | { type: 'enqueue'; node: IGridNode; direction: TraceDirection; priority?: number; g?: number; h?: number; f?: number }
+    | { type: 'dequeue'; node: IGridNode; direction: TraceDirection; g?: number; h?: number; f?: number }
+    | { type: 'relax'; node: IGridNode; from: IGridNode; direction: TraceDirection; oldG?: number; newG: number; h?: number; f?: number }
+    | { type: 'close'; node: IGridNode; direction: TraceDirection }
+    | { type: 'meet'; node: IGridNode; forwardG?: number; backwardG?: number };
+"#;
+
+#[wasm_bindgen(typescript_custom_section)]
+const ISEARCH_STEP: &'static str = r#"
+interface ISearchStep {
+    processed: IGridNode[];
+    frontier: IGridNode[];
+    path: IGridNode[];
+    finished: boolean;
+}
+"#;
+
+#[wasm_bindgen(typescript_custom_section)]
+const IGRID_SNAPSHOT: &'static str = r#"
+interface IGridSnapshot {
+    version?: number;
+    width: number;
+    height: number;
+    weight?: number;
+    walls: string[];
+    weights: string[];
+}
+"#;
+
+#[wasm_bindgen(typescript_custom_section)]
+const ISCENARIO_QUERY: &'static str = r#"
+interface IScenarioQuery {
+    bucket: number;
+    map: string;
+    mapWidth: number;
+    mapHeight: number;
+    startX: number;
+    startY: number;
+    goalX: number;
+    goalY: number;
+    optimalLength: number;
+}
+"#;
+
+#[wasm_bindgen(typescript_custom_section)]
+const IPOINT: &'static str = r#"
+interface IPoint {
+    x: number;
+    y: number;
+}
+"#;
+
+#[wasm_bindgen(typescript_custom_section)]
+const IASCII_OVERLAY: &'static str = r#"
+interface IAsciiOverlay {
+    start?: IPoint;
+    end?: IPoint;
+    result?: {
+        path: IGridNode[];
+        processed: IGridNode[];
+    };
+}
+"#;
+
+#[wasm_bindgen(typescript_custom_section)]
+const IMAZE_OPTIONS: &'static str = r#"
+interface IMazeOptions {
+    caveFill?: number;
+    /** Smoothing passes for caves, from 0 to 10. */
+    cavePasses?: number;
+    braid?: number;
+    doorwayWeight?: number;
+    clear?: boolean;
+    start?: IPoint;
+    end?: IPoint;
+}
+"#;
+
+#[wasm_bindgen(typescript_custom_section)]
+const ITERRAIN_OPTIONS: &'static str = r#"
+interface ITerrainOptions {
+    octaves?: number;
+    scale?: number;
+    persistence?: number;
+    minWeight?: number;
+    maxWeight?: number;
+}
+"#;
+
+#[wasm_bindgen(typescript_custom_section)]
+const IMAZE_CONSTRUCTION: &'static str = r#"
+type IMazeEvent =
+    | { type: 'wallAdded'; node: IGridNode }
+    | { type: 'wallRemoved'; node: IGridNode }
+    | { type: 'cellCarved'; node: IGridNode };
+
+interface IMazeConstruction {
+    maze: IGridNode[];
+    startsFilled: boolean;
+    events: IMazeEvent[];
+}
+"#;
+
+#[wasm_bindgen(typescript_custom_section)]
+const IUNIVERSE_ERROR: &'static str = r#"
+type UniverseErrorCode =
+    | 'outOfBounds'
+    | 'startBlocked'
+    | 'goalBlocked'
+    | 'unsupportedSearch'
+    | 'invalidOptions'
+    | 'invalidSnapshot'
+    | 'invalidMap'
+    | 'invalidScenario'
+    | 'invalidAscii';
+
+/** Thrown by every fallible universe method. */
+interface IUniverseError {
+    code: UniverseErrorCode;
+    message: string;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "IPathResponse")]
+    pub type IPathResponse;
+
+    #[wasm_bindgen(typescript_type = "IGridNode[]")]
+    pub type IMazeResponse;
+
+    #[wasm_bindgen(typescript_type = "IGridSnapshot")]
+    pub type IGridSnapshot;
+
+    #[wasm_bindgen(typescript_type = "IMazeOptions")]
+    pub type IMazeOptions;
+
+    #[wasm_bindgen(typescript_type = "IMazeConstruction")]
+    pub type IMazeConstruction;
+
+    #[wasm_bindgen(typescript_type = "ITerrainOptions")]
+    pub type ITerrainOptions;
+
+    #[wasm_bindgen(typescript_type = "ISearchStep")]
+    pub type ISearchStep;
+
+    #[wasm_bindgen(typescript_type = "IGridNode[]")]
+    pub type IGridNodeList;
+
+    #[wasm_bindgen(typescript_type = "IScenarioQuery[]")]
+    pub type IScenarioQueryList;
+
+    #[wasm_bindgen(typescript_type = "IPoint")]
+    pub type IPoint;
+
+    #[wasm_bindgen(typescript_type = "IAsciiOverlay")]
+    pub type IAsciiOverlay;
+}
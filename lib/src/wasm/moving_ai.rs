@@ -0,0 +1,24 @@
+use wasm_bindgen::{prelude::wasm_bindgen, JsCast, JsValue};
+
+use crate::universe::{moving_ai, ScenarioQuery, UniverseError};
+
+use super::IScenarioQueryList;
+
+#[wasm_bindgen(js_name = parseMovingAiScenario)]
+pub fn parse_moving_ai_scenario(scenario: &str) -> Result<IScenarioQueryList, JsValue> {
+    let queries = moving_ai::parse_scenario(scenario)?;
+
+    return Ok(serde_wasm_bindgen::to_value(&queries)
+        .unwrap()
+        .unchecked_into());
+}
+
+#[wasm_bindgen(js_name = writeMovingAiScenario)]
+pub fn write_moving_ai_scenario(queries: IScenarioQueryList) -> Result<String, JsValue> {
+    let queries: Vec<ScenarioQuery> =
+        serde_wasm_bindgen::from_value(queries.into()).map_err(|err| {
+            UniverseError::InvalidScenario(format!("invalid scenario queries: {}", err))
+        })?;
+
+    return Ok(moving_ai::write_scenario(&queries));
+}
@@ -0,0 +1,53 @@
+use wasm_bindgen::{prelude::wasm_bindgen, JsCast, JsValue};
+
+use crate::universe::{PathFindingAlgorithm, SearchSession, Universe};
+
+use super::{IGridNodeList, ISearchStep};
+
+#[wasm_bindgen]
+impl SearchSession {
+    #[wasm_bindgen(constructor)]
+    pub fn new_js(
+        universe: &Universe,
+        start_x: i32,
+        start_y: i32,
+        end_x: i32,
+        end_y: i32,
+        algorithm: PathFindingAlgorithm,
+    ) -> Result<SearchSession, JsValue> {
+        return Ok(SearchSession::new(
+            universe, start_x, start_y, end_x, end_y, algorithm,
+        )?);
+    }
+
+    #[wasm_bindgen(js_name = step)]
+    pub fn step_js(&mut self) -> ISearchStep {
+        return self.step_n_js(1);
+    }
+
+    #[wasm_bindgen(js_name = stepN)]
+    pub fn step_n_js(&mut self, count: u32) -> ISearchStep {
+        return serde_wasm_bindgen::to_value(&self.step_n(count))
+            .unwrap()
+            .unchecked_into();
+    }
+
+    #[wasm_bindgen(js_name = frontier)]
+    pub fn frontier_js(&self) -> IGridNodeList {
+        return serde_wasm_bindgen::to_value(&self.frontier())
+            .unwrap()
+            .unchecked_into();
+    }
+
+    #[wasm_bindgen(js_name = path)]
+    pub fn path_js(&self) -> IGridNodeList {
+        return serde_wasm_bindgen::to_value(self.path())
+            .unwrap()
+            .unchecked_into();
+    }
+
+    #[wasm_bindgen(getter = finished)]
+    pub fn finished_js(&self) -> bool {
+        return self.finished();
+    }
+}
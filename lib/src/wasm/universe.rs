@@ -0,0 +1,210 @@
+use wasm_bindgen::{prelude::wasm_bindgen, JsCast, JsValue};
+
+use crate::universe::{
+    AsciiGrid, GridNode, GridSnapshot, Heuristic, MazeOptions, MazeType, PathFindingAlgorithm,
+    TerrainOptions, Universe, UniverseError,
+};
+
+use super::ascii::JsAsciiOverlay;
+use super::{
+    IAsciiOverlay, IGridNodeList, IGridSnapshot, IMazeConstruction, IMazeOptions, IMazeResponse,
+    IPathResponse, ITerrainOptions,
+};
+
+#[wasm_bindgen]
+impl Universe {
+    #[wasm_bindgen(constructor)]
+    pub fn new_js(width: u32, height: u32) -> Universe {
+        return Universe::new(width, height);
+    }
+
+    #[wasm_bindgen(js_name = resize)]
+    pub fn resize_js(&mut self, width: u32, height: u32) {
+        self.resize(width, height);
+    }
+
+    #[wasm_bindgen(js_name = reset)]
+    pub fn reset_js(&mut self) {
+        self.reset();
+    }
+
+    #[wasm_bindgen(js_name = getNode)]
+    pub fn get_node_js(&self, x: i32, y: i32) -> Result<GridNode, JsValue> {
+        return Ok(self.get_node(x, y)?);
+    }
+
+    #[wasm_bindgen(js_name = hasNode)]
+    pub fn has_node_js(&self, x: i32, y: i32) -> bool {
+        return self.has_node(x, y);
+    }
+
+    /// See `Universe::generate_maze`.
+    #[wasm_bindgen(js_name = generateMaze)]
+    pub fn generate_maze_js(
+        &self,
+        maze_type: MazeType,
+        seed: Option<u32>,
+        options: Option<IMazeOptions>,
+    ) -> Result<IMazeResponse, JsValue> {
+        let maze = self.generate_maze(maze_type, seed, &parse_maze_options(options)?)?;
+
+        return Ok(serde_wasm_bindgen::to_value(&maze)
+            .unwrap()
+            .unchecked_into());
+    }
+
+    /// See `Universe::generate_maze_construction`. Replaying `events` in order animates the maze being built.
+    #[wasm_bindgen(js_name = generateMazeConstruction)]
+    pub fn generate_maze_construction_js(
+        &self,
+        maze_type: MazeType,
+        seed: Option<u32>,
+        options: Option<IMazeOptions>,
+    ) -> Result<IMazeConstruction, JsValue> {
+        let construction =
+            self.generate_maze_construction(maze_type, seed, &parse_maze_options(options)?)?;
+
+        return Ok(serde_wasm_bindgen::to_value(&construction)
+            .unwrap()
+            .unchecked_into());
+    }
+
+    /// See `Universe::apply_maze`.
+    #[wasm_bindgen(js_name = applyMaze)]
+    pub fn apply_maze_js(
+        &mut self,
+        maze_type: MazeType,
+        seed: Option<u32>,
+        options: Option<IMazeOptions>,
+    ) -> Result<IGridNodeList, JsValue> {
+        let diff = self.apply_maze(maze_type, seed, &parse_maze_options(options)?)?;
+
+        return Ok(serde_wasm_bindgen::to_value(&diff)
+            .unwrap()
+            .unchecked_into());
+    }
+
+    /// See `Universe::generate_terrain`.
+    #[wasm_bindgen(js_name = generateTerrain)]
+    pub fn generate_terrain_js(
+        &self,
+        seed: Option<u32>,
+        options: Option<ITerrainOptions>,
+    ) -> Result<IGridNodeList, JsValue> {
+        let options: TerrainOptions = match options {
+            Some(options) => serde_wasm_bindgen::from_value(options.into()).map_err(|err| {
+                UniverseError::InvalidOptions(format!("invalid terrain options: {}", err))
+            })?,
+            None => TerrainOptions::default(),
+        };
+
+        let terrain = self.generate_terrain(seed, &options)?;
+
+        return Ok(serde_wasm_bindgen::to_value(&terrain)
+            .unwrap()
+            .unchecked_into());
+    }
+
+    #[wasm_bindgen(js_name = findPath)]
+    pub fn find_path_js(
+        &self,
+        start_x: i32,
+        start_y: i32,
+        end_x: i32,
+        end_y: i32,
+        algorithm: PathFindingAlgorithm,
+        trace: Option<bool>,
+    ) -> Result<IPathResponse, JsValue> {
+        let path = self.find_path(
+            start_x,
+            start_y,
+            end_x,
+            end_y,
+            algorithm,
+            trace.unwrap_or(false),
+        )?;
+
+        return Ok(serde_wasm_bindgen::to_value(&path)
+            .unwrap()
+            .unchecked_into());
+    }
+
+    #[wasm_bindgen(js_name = setWeight)]
+    pub fn set_weight_js(&mut self, x: i32, y: i32, weight: i32) -> Result<(), JsValue> {
+        return Ok(self.set_weight(x, y, weight)?);
+    }
+
+    #[wasm_bindgen(js_name = setPassable)]
+    pub fn set_passable_js(&mut self, x: i32, y: i32, passable: bool) -> Result<(), JsValue> {
+        return Ok(self.set_passable(x, y, passable)?);
+    }
+
+    /// See `Universe::get_heuristic`.
+    #[wasm_bindgen(js_name = getHeuristic)]
+    pub fn get_heuristic_js(&self) -> Heuristic {
+        return self.get_heuristic();
+    }
+
+    /// See `Universe::set_heuristic`. Passing `undefined` restores the default.
+    #[wasm_bindgen(js_name = setHeuristic)]
+    pub fn set_heuristic_js(&mut self, heuristic: Option<Heuristic>) {
+        self.set_heuristic(heuristic);
+    }
+
+    #[wasm_bindgen(js_name = getSnapshot)]
+    pub fn get_snapshot_js(&self) -> IGridSnapshot {
+        return serde_wasm_bindgen::to_value(&self.get_snapshot())
+            .unwrap()
+            .unchecked_into();
+    }
+
+    /// See `Universe::load_snapshot`. Throws `invalidSnapshot` if the value is not shaped like an `IGridSnapshot`.
+    #[wasm_bindgen(js_name = loadSnapshot)]
+    pub fn load_snapshot_js(&mut self, snapshot: IGridSnapshot) -> Result<(), JsValue> {
+        let snapshot: GridSnapshot = serde_wasm_bindgen::from_value(snapshot.into())
+            .map_err(|err| UniverseError::InvalidSnapshot(format!("invalid snapshot: {}", err)))?;
+
+        return Ok(self.load_snapshot(&snapshot)?);
+    }
+
+    /// See `Universe::from_moving_ai_map`.
+    #[wasm_bindgen(js_name = fromMovingAiMap)]
+    pub fn from_moving_ai_map_js(map: &str) -> Result<Universe, JsValue> {
+        return Ok(Universe::from_moving_ai_map(map)?);
+    }
+
+    /// See `Universe::to_moving_ai_map`.
+    #[wasm_bindgen(js_name = toMovingAiMap)]
+    pub fn to_moving_ai_map_js(&self) -> String {
+        return self.to_moving_ai_map();
+    }
+
+    /// See `Universe::from_ascii`.
+    #[wasm_bindgen(js_name = fromAscii)]
+    pub fn from_ascii_js(ascii: &str) -> Result<AsciiGrid, JsValue> {
+        return Ok(Universe::from_ascii(ascii)?);
+    }
+
+    /// See `Universe::to_ascii`.
+    #[wasm_bindgen(js_name = toAscii)]
+    pub fn to_ascii_js(&self) -> String {
+        return self.to_ascii();
+    }
+
+    /// See `Universe::to_ascii_with_overlay`. Only the path and processed nodes of `overlay.result` are read.
+    #[wasm_bindgen(js_name = toAsciiWithOverlay)]
+    pub fn to_ascii_with_overlay_js(&self, overlay: IAsciiOverlay) -> Result<String, JsValue> {
+        let overlay = JsAsciiOverlay::parse(overlay)?;
+        let result = overlay.path_result();
+
+        return Ok(self.to_ascii_with_overlay(&overlay.overlay(result.as_ref())));
+    }
+}
+
+fn parse_maze_options(options: Option<IMazeOptions>) -> Result<MazeOptions, UniverseError> {
+    return match options {
+        Some(options) => serde_wasm_bindgen::from_value(options.into())
+            .map_err(|err| UniverseError::InvalidOptions(format!("invalid maze options: {}", err))),
+        None => Ok(MazeOptions::default()),
+    };
+}
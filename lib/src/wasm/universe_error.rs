@@ -0,0 +1,22 @@
+use serde::Serialize;
+use wasm_bindgen::JsValue;
+
+use crate::universe::UniverseError;
+
+/// The `IUniverseError` object thrown to JS.
+#[derive(Serialize)]
+struct UniverseErrorPayload {
+    code: &'static str,
+    message: String,
+}
+
+impl From<UniverseError> for JsValue {
+    fn from(err: UniverseError) -> Self {
+        let payload = UniverseErrorPayload {
+            code: err.code(),
+            message: err.to_string(),
+        };
+
+        return serde_wasm_bindgen::to_value(&payload).unwrap();
+    }
+}